@@ -0,0 +1,59 @@
+use adventofcode::conway::{active, neigh_weights, Simulation};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const ROUNDS: u16 = 6;
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let initial = active(include_str!("../cases/17.in")).expect("couldn't parse grid");
+
+    let mut group = c.benchmark_group("neigh weights");
+    for dimensions in 3..=8 {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(dimensions),
+            &dimensions,
+            |b, &dimensions| b.iter(|| neigh_weights(black_box(dimensions), black_box(ROUNDS))),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("simulation");
+    group.sample_size(20);
+    for dimensions in 3..=8 {
+        for rounds in [2, 4, ROUNDS] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{} dimensions", dimensions), rounds),
+                &(dimensions, rounds),
+                |b, &(dimensions, rounds)| {
+                    b.iter(|| Simulation::new(dimensions, rounds, black_box(&initial)).run())
+                },
+            );
+        }
+    }
+    group.finish();
+
+    // Serial against parallel stepping, on as many threads as there are CPUs (at least 2).
+    // Steps use no more threads than there are CPUs,
+    // so on a single-CPU machine both run serially and this shows no speedup.
+    let cpus = std::thread::available_parallelism().map_or(2, |n| n.get().max(2));
+    let mut group = c.benchmark_group("threads");
+    group.sample_size(10);
+    for dimensions in [5, 6, 7] {
+        for threads in [1, cpus] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{} dimensions", dimensions), threads),
+                &(dimensions, threads),
+                |b, &(dimensions, threads)| {
+                    b.iter(|| {
+                        let mut sim = Simulation::new(dimensions, ROUNDS, black_box(&initial));
+                        sim.threads = threads;
+                        sim.run()
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -4,8 +4,9 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use std::collections::HashSet;
 
 pub fn criterion_benchmark(c: &mut Criterion) {
+    // Fall back to the bundled example if the private input isn't checked out.
     let s = std::fs::read_to_string("../adventofcode-common/secret-cases/2020/01p.in")
-        .expect("couldn't read file");
+        .unwrap_or_else(|_| include_str!("../cases/01.in").to_string());
     let nums: Vec<_> = s
         .lines()
         .map(|line| line.parse::<u32>().expect("can't parse integer"))
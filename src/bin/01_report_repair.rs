@@ -1,33 +1,30 @@
-use std::collections::HashSet;
+use adventofcode::day01::{self, Day01};
+use adventofcode::solution::{Solution, Timings};
+use adventofcode::OrExit;
 
-fn pair(nums: &[u32], nums_set: &HashSet<u32>) -> Vec<u32> {
-    let mut ans = Vec::new();
-    for num in nums {
-        let needed = 2020 - num;
-        if needed < *num {
-            continue;
-        }
-        if nums_set.contains(&needed) {
-            ans.push(num * needed);
-        }
-    }
-    ans
+fn lines(xs: &[u32]) -> String {
+    xs.iter().map(u32::to_string).collect::<Vec<_>>().join("\n")
 }
 
 fn main() {
-    let nums =
-        adventofcode::read_input_lines(|line| line.parse::<u32>().expect("can't parse integer"));
+    let opts = adventofcode::opts::from_env(&[]);
+    let source = opts.source().or_exit();
+    let input = source.read().or_exit();
+    let mut timings = Timings::new(&opts, Day01::DAY, Day01::NAME);
+    let nums = timings
+        .parse(|| Day01::parse(&input))
+        .map_err(|e| e.in_file(source.name()))
+        .or_exit();
     let nums_set = nums.iter().cloned().collect();
 
-    for x in pair(&nums, &nums_set) {
-        println!("{}", x);
-    }
-    let try1 = adventofcode::day01::by_combinations(&nums, &nums_set);
-    let try2 = adventofcode::day01::over_input_range(&nums, &nums_set);
-    let try3 = adventofcode::day01::by_combinations_sorted(&nums, &nums_set);
-    assert_eq!(try1, try2);
-    assert_eq!(try1, try3);
-    for x in try1 {
-        println!("{}", x);
-    }
+    timings.part(1, || Ok(lines(&day01::pair(&nums))));
+    timings.part(2, || {
+        let try1 = day01::by_combinations(&nums, &nums_set);
+        let try2 = day01::over_input_range(&nums, &nums_set);
+        let try3 = day01::by_combinations_sorted(&nums, &nums_set);
+        assert_eq!(try1, try2);
+        assert_eq!(try1, try3);
+        Ok(lines(&try1))
+    });
+    timings.finish();
 }
@@ -0,0 +1,53 @@
+use adventofcode::day03::{trees_on_slopes, Day03, Slope, PART1_SLOPES, PART2_SLOPES};
+use adventofcode::opts::Param;
+use adventofcode::solution::{Solution, Timings};
+use adventofcode::{Error, NumberMode, OrExit};
+
+const PARAMS: &[Param] = &[Param {
+    short: Some('s'),
+    long: "slopes",
+    value: Some("R,D..."),
+    help: "count trees on these (right, down) slopes instead, e.g. 3,1,1,2",
+}];
+
+fn slopes(s: &str) -> adventofcode::Result<Vec<Slope>> {
+    let nums = adventofcode::numbers_with::<usize>(s, NumberMode::Unsigned)?;
+    if nums.len() % 2 != 0 || nums.chunks(2).any(|rd| rd[1] == 0) {
+        return Err(Error::invalid(format!(
+            "--slopes needs pairs of right and nonzero down, not {}",
+            s
+        )));
+    }
+    Ok(nums.chunks(2).map(|rd| (rd[0], rd[1])).collect())
+}
+
+fn main() {
+    let opts = adventofcode::opts::from_env(PARAMS);
+    let custom = opts
+        .get::<String>("slopes")
+        .or_exit()
+        .map(|s| slopes(&s).or_exit());
+    let source = opts.source().or_exit();
+    let input = source.read().or_exit();
+    let mut timings = Timings::new(&opts, Day03::DAY, Day03::NAME);
+    let trees = timings
+        .parse(|| Day03::parse(&input))
+        .map_err(|e| e.in_file(source.name()))
+        .or_exit();
+
+    if let Some(custom) = custom {
+        timings.part(opts.part.unwrap_or(1), || {
+            let (counts, product) = trees_on_slopes(&trees, &custom);
+            let counts: Vec<_> = counts.iter().map(usize::to_string).collect();
+            Ok(format!("{}\n{}", counts.join(" "), product))
+        });
+    } else {
+        timings.part(1, || {
+            Ok(trees_on_slopes(&trees, PART1_SLOPES).1.to_string())
+        });
+        timings.part(2, || {
+            Ok(trees_on_slopes(&trees, PART2_SLOPES).1.to_string())
+        });
+    }
+    timings.finish();
+}
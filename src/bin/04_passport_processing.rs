@@ -0,0 +1,26 @@
+use adventofcode::day04::Day04;
+use adventofcode::solution::{Solution, Timings};
+use adventofcode::OrExit;
+
+fn main() {
+    let opts = adventofcode::opts::from_env(&[]);
+    let source = opts.source().or_exit();
+    let input = source.read().or_exit();
+    let mut timings = Timings::new(&opts, Day04::DAY, Day04::NAME);
+    let passports = timings
+        .parse(|| Day04::parse(&input))
+        .map_err(|e| e.in_file(source.name()))
+        .or_exit();
+
+    if opts.verbose {
+        for p in &passports {
+            if let Some(problem) = p.problem() {
+                eprintln!("{}:{}: {}", source.name(), p.line, problem);
+            }
+        }
+    }
+
+    timings.part(1, || Day04::part1(&passports));
+    timings.part(2, || Day04::part2(&passports));
+    timings.finish();
+}
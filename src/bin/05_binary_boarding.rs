@@ -0,0 +1,35 @@
+use adventofcode::day05::{missing_seat, plane, Day05, COLUMNS};
+use adventofcode::opts::Param;
+use adventofcode::solution::{Solution, Timings};
+use adventofcode::OrExit;
+
+const PARAMS: &[Param] = &[Param {
+    short: Some('m'),
+    long: "map",
+    value: None,
+    help: "print which seats are taken (#), empty (.) and the missing one (O), front row first, on standard error",
+}];
+
+fn main() {
+    let opts = adventofcode::opts::from_env(PARAMS);
+    let source = opts.source().or_exit();
+    let input = source.read().or_exit();
+    let mut timings = Timings::new(&opts, Day05::DAY, Day05::NAME);
+    let ids = timings
+        .parse(|| Day05::parse(&input))
+        .map_err(|e| e.in_file(source.name()))
+        .or_exit();
+
+    if opts.flag("map") {
+        let mut map = plane(&ids).map(|&taken| if taken { '#' } else { '.' });
+        if let Some(id) = missing_seat(&ids) {
+            let id = usize::from(id);
+            map[(id % COLUMNS, id / COLUMNS)] = 'O';
+        }
+        eprint!("{}", map.render(|&c| c));
+    }
+
+    timings.part(1, || Day05::part1(&ids));
+    timings.part(2, || Day05::part2(&ids));
+    timings.finish();
+}
@@ -0,0 +1,3 @@
+fn main() {
+    adventofcode::solution::main::<adventofcode::day06::Day06>();
+}
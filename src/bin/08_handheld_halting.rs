@@ -0,0 +1,45 @@
+use adventofcode::day08::{repaired, Day08};
+use adventofcode::handheld::{self, Instruction};
+use adventofcode::opts::Param;
+use adventofcode::solution::{Solution, Timings};
+use adventofcode::OrExit;
+
+const PARAMS: &[Param] = &[Param {
+    short: Some('t'),
+    long: "trace",
+    value: None,
+    help: "print each instruction run, with the accumulator before it, on standard error",
+}];
+
+fn trace(pc: usize, inst: &Instruction, acc: i64) {
+    eprintln!("{:4} {:?} {:+} acc={}", pc, inst.op, inst.arg, acc);
+}
+
+fn main() {
+    let opts = adventofcode::opts::from_env(PARAMS);
+    let tracing = opts.flag("trace");
+    let source = opts.source().or_exit();
+    let input = source.read().or_exit();
+    let mut timings = Timings::new(&opts, Day08::DAY, Day08::NAME);
+    let program = timings
+        .parse(|| Day08::parse(&input))
+        .map_err(|e| e.in_file(source.name()))
+        .or_exit();
+
+    if tracing {
+        if opts.wants_part(1) {
+            eprintln!("original:");
+            handheld::run_traced(&program, trace).or_exit();
+        }
+        if opts.wants_part(2) {
+            if let Ok(fixed) = repaired(&program) {
+                eprintln!("repaired:");
+                handheld::run_traced(&fixed, trace).or_exit();
+            }
+        }
+    }
+
+    timings.part(1, || Day08::part1(&program));
+    timings.part(2, || Day08::part2(&program));
+    timings.finish();
+}
@@ -0,0 +1,30 @@
+use adventofcode::day09::{first_invalid, weakness, Day09, PREAMBLE};
+use adventofcode::opts::Param;
+use adventofcode::solution::{Solution, Timings};
+use adventofcode::OrExit;
+
+const PARAMS: &[Param] = &[Param {
+    short: Some('n'),
+    long: "preamble",
+    value: Some("N"),
+    help: "check each number against the N before it (default 25; the example uses 5)",
+}];
+
+fn main() {
+    let opts = adventofcode::opts::from_env(PARAMS);
+    let preamble: usize = opts.get_or("preamble", PREAMBLE).or_exit();
+    let source = opts.source().or_exit();
+    let input = source.read().or_exit();
+    let mut timings = Timings::new(&opts, Day09::DAY, Day09::NAME);
+    let nums = timings
+        .parse(|| Day09::parse(&input))
+        .map_err(|e| e.in_file(source.name()))
+        .or_exit();
+
+    timings.part(1, || Ok(first_invalid(&nums, preamble)?.to_string()));
+    // Finding the weakness needs part 1's answer, so this finds it again.
+    timings.part(2, || {
+        Ok(weakness(&nums, first_invalid(&nums, preamble)?)?.to_string())
+    });
+    timings.finish();
+}
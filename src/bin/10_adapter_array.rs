@@ -0,0 +1,3 @@
+fn main() {
+    adventofcode::solution::main::<adventofcode::day10::Day10>();
+}
@@ -0,0 +1,40 @@
+use adventofcode::day11::{run_until_stable, Day11, Sight};
+use adventofcode::opts::Param;
+use adventofcode::solution::{Solution, Timings};
+use adventofcode::OrExit;
+
+const PARAMS: &[Param] = &[Param {
+    short: Some('g'),
+    long: "generations",
+    value: None,
+    help: "print every generation until the seating is stable, on standard error",
+}];
+
+fn main() {
+    let opts = adventofcode::opts::from_env(PARAMS);
+    let print = opts.flag("generations");
+    let source = opts.source().or_exit();
+    let input = source.read().or_exit();
+    let mut timings = Timings::new(&opts, Day11::DAY, Day11::NAME);
+    let seats = timings
+        .parse(|| Day11::parse(&input))
+        .map_err(|e| e.in_file(source.name()))
+        .or_exit();
+
+    for (part, sight) in [(1, Sight::Adjacent), (2, Sight::LineOfSight)] {
+        timings.part(part, || {
+            let occupied = run_until_stable(&seats, sight, |t, occupied| {
+                if print {
+                    eprintln!(
+                        "part {} generation {}:\n{}",
+                        part,
+                        t,
+                        seats.render(occupied)
+                    );
+                }
+            });
+            Ok(occupied.iter().filter(|&&occ| occ).count().to_string())
+        });
+    }
+    timings.finish();
+}
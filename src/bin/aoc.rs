@@ -0,0 +1,100 @@
+use adventofcode::input::Source;
+use adventofcode::solution::{find, format_ms, Entry, Report, SOLUTIONS};
+use adventofcode::OrExit;
+
+const USAGE: &str = "usage: aoc [--json] <day> [input file | -e input text]
+       aoc [--json] all [directory containing <day>.in files, default cases]
+
+--json prints one line of JSON per day instead of a table.";
+
+fn print_header(json: bool) {
+    if json {
+        return;
+    }
+    println!(
+        "{:>3}  {:<24} {:>12} {:>16} {:>12} {:>16} {:>12}",
+        "day", "name", "parse", "part 1", "time", "part 2", "time"
+    );
+}
+
+fn print_report(report: &Report, json: bool) {
+    if json {
+        println!("{}", report.to_json());
+        return;
+    }
+    print!(
+        "{:>3}  {:<24} {:>12}",
+        report.day,
+        report.name,
+        format_ms(report.parse_time)
+    );
+    for part in &report.parts {
+        print!(" {:>16} {:>12}", part.answer, format_ms(part.time));
+    }
+    println!();
+}
+
+fn run(entry: &Entry, source: &Source) -> adventofcode::Result<Report> {
+    let input = source.read()?;
+    (entry.run)(&input).map_err(|e| e.in_file(source.name()))
+}
+
+fn main() {
+    let opts = match adventofcode::opts::parse(&[], std::env::args().skip(1)) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if opts.part.is_some() {
+        eprintln!("error: aoc always runs both parts\n{}", USAGE);
+        std::process::exit(2);
+    }
+    let args = &opts.args;
+    let Some(which) = args.first() else {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    };
+
+    if which == "all" {
+        let dir = args.get(1).map_or("cases", String::as_str);
+        print_header(opts.json);
+        let mut failed = false;
+        for entry in SOLUTIONS {
+            let source = Source::File(format!("{}/{:02}.in", dir, entry.day));
+            match run(entry, &source) {
+                Ok(report) => print_report(&report, opts.json),
+                Err(e) => {
+                    eprintln!("error: day {}: {}", entry.day, e);
+                    failed = true;
+                }
+            }
+        }
+        if failed {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let day: u8 = match which.parse() {
+        Ok(day) => day,
+        Err(_) => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    let Some(entry) = find(day) else {
+        let days: Vec<_> = SOLUTIONS.iter().map(|e| e.day.to_string()).collect();
+        eprintln!("no solution for day {}; have {}", day, days.join(", "));
+        std::process::exit(2);
+    };
+    let source = Source::from_args(args[1..].iter().cloned()).or_exit();
+    let report = run(entry, &source).or_exit();
+    print_header(opts.json);
+    print_report(&report, opts.json);
+}
@@ -0,0 +1,143 @@
+// Sets of small numbers, one bit per possible member.
+
+use crate::{Error, Result};
+use std::ops::{BitAnd, BitOr};
+
+// Any size, fixed when created.
+pub struct Bitset {
+    bits: Vec<u32>,
+}
+
+impl Bitset {
+    const BLOCKSIZE: usize = u32::BITS as usize;
+
+    // Room for 0..n.
+    pub fn new(n: usize) -> Self {
+        Self {
+            bits: vec![0; n.div_ceil(Self::BLOCKSIZE)],
+        }
+    }
+
+    pub fn test(&self, i: usize) -> bool {
+        let block = i / Self::BLOCKSIZE;
+        let in_block = 1 << (i % Self::BLOCKSIZE);
+        self.bits[block] & in_block == in_block
+    }
+
+    pub fn set(&mut self, i: usize) {
+        let block = i / Self::BLOCKSIZE;
+        let in_block = 1 << (i % Self::BLOCKSIZE);
+        self.bits[block] |= in_block;
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&b| b == 0)
+    }
+}
+
+// Lowercase letters a to z, as 26 bits of a u32.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Letters(u32);
+
+impl Letters {
+    pub const NONE: Self = Self(0);
+    pub const ALL: Self = Self((1 << 26) - 1);
+
+    pub fn contains(self, c: char) -> bool {
+        c.is_ascii_lowercase() && self.0 & Self::bit(c) != 0
+    }
+
+    pub fn insert(&mut self, c: char) {
+        assert!(c.is_ascii_lowercase(), "{:?} isn't a lowercase letter", c);
+        self.0 |= Self::bit(c);
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    fn bit(c: char) -> u32 {
+        1 << (c as u8 - b'a')
+    }
+}
+
+impl std::str::FromStr for Letters {
+    type Err = Error;
+
+    // Repeated letters are fine; anything that isn't a lowercase letter isn't.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut letters = Self::NONE;
+        for (i, c) in s.chars().enumerate() {
+            if !c.is_ascii_lowercase() {
+                return Err(Error::parse(s, "expected only lowercase letters").at_column(i + 1));
+            }
+            letters.insert(c);
+        }
+        Ok(letters)
+    }
+}
+
+impl BitOr for Letters {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Letters {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitset_across_blocks() {
+        let mut bits = Bitset::new(100);
+        assert!(bits.is_empty());
+        for i in [0, 31, 32, 99] {
+            bits.set(i);
+        }
+        bits.set(32);
+        assert_eq!(bits.len(), 4);
+        assert!(bits.test(31) && bits.test(32) && bits.test(99));
+        assert!(!bits.test(1) && !bits.test(33) && !bits.test(98));
+    }
+
+    #[test]
+    fn letters() {
+        let ab: Letters = "abba".parse().unwrap();
+        let bz: Letters = "zb".parse().unwrap();
+        assert_eq!(ab.len(), 2);
+        assert_eq!((ab | bz).len(), 3);
+        assert_eq!(ab & bz, "b".parse().unwrap());
+        assert!((ab | bz).contains('z') && !ab.contains('z'));
+        assert!(!ab.contains('A'));
+        assert_eq!(Letters::ALL.len(), 26);
+        assert_eq!(Letters::ALL & ab, ab);
+        assert!(Letters::NONE.is_empty());
+    }
+
+    #[test]
+    fn letters_invalid() {
+        assert_eq!("abC".parse::<Letters>().unwrap_err().column, Some(3));
+    }
+}
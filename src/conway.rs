@@ -0,0 +1,1188 @@
+use crate::grid::{hash_or_dot, Grid};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+pub mod naive;
+
+pub type Dim = u16;
+pub type Time = u16;
+pub type Coord = i32;
+// x and y take enough bits for the input plus growth each round;
+// the other dimensions take in total (1 + nrounds) * log_2(dimensions).
+// That limits the number of rounds: see max_rounds.
+pub type Pos = i64;
+// Since only 0, 1, 2, 3, 4+ matter, can use a u8 and saturating adds.
+// This doesn't really appear to make a performance difference though,
+// vs this u32 and not using saturating adds.
+// u32 will support up to 20 dimensions (3 ** 20 < 2 ** 32 - 1)
+pub type NeighCount = u32;
+
+type NeighMap = HashMap<Pos, HashMap<Pos, NeighCount>>;
+pub type CollapsedNeighMap = HashMap<Pos, Vec<(Pos, NeighCount)>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Behaviour {
+    // The state at generation first + period is the same as at generation first.
+    Periodic { first: Time, period: Time },
+    // No generation repeated before reaching the round limit.
+    Unresolved,
+}
+
+// A life-like rule: the neighbour counts at which a dead cell becomes alive,
+// and at which a live cell stays alive. Written B3/S23, which is Conway's Life.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    // Bit n is set if n neighbours is enough; counts above MAX_COUNT never are,
+    // since B/S notation writes each count as one digit.
+    born: u32,
+    survives: u32,
+}
+
+impl Rule {
+    pub const LIFE: Self = Self {
+        born: 1 << 3,
+        survives: 1 << 2 | 1 << 3,
+    };
+
+    pub const MAX_COUNT: NeighCount = 9;
+
+    pub fn new(born: &[NeighCount], survives: &[NeighCount]) -> Self {
+        let mask = |counts: &[NeighCount]| {
+            counts.iter().fold(0, |mask, &n| {
+                assert!(
+                    n <= Self::MAX_COUNT,
+                    "can't have a rule for {} neighbours, only 0 to {}",
+                    n,
+                    Self::MAX_COUNT
+                );
+                mask | 1 << n
+            })
+        };
+        Self {
+            born: mask(born),
+            survives: mask(survives),
+        }
+    }
+
+    // Whether a cell is alive next generation.
+    pub fn next(&self, alive: bool, neighbours: NeighCount) -> bool {
+        let counts = if alive { self.survives } else { self.born };
+        neighbours <= Self::MAX_COUNT && counts >> neighbours & 1 == 1
+    }
+
+    // Whether cells with no neighbours are always dead next generation.
+    pub fn needs_neighbours(&self) -> bool {
+        !self.next(false, 0) && !self.next(true, 0)
+    }
+}
+
+impl std::str::FromStr for Rule {
+    type Err = crate::Error;
+
+    // B3/S23
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let err = || crate::Error::parse(s, "expected a rule like B3/S23");
+        let (born, survives) = s.split_once('/').ok_or_else(err)?;
+        let counts = |part: &str, prefix: char| -> crate::Result<Vec<NeighCount>> {
+            let digits = part.strip_prefix(prefix).ok_or_else(err)?;
+            digits
+                .chars()
+                .map(|c| c.to_digit(10).ok_or_else(err))
+                .collect()
+        };
+        Ok(Self::new(&counts(born, 'B')?, &counts(survives, 'S')?))
+    }
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let digits = |mask: u32| -> String {
+            (0..=Self::MAX_COUNT)
+                .filter(|n| mask >> n & 1 == 1)
+                .map(|n| n.to_string())
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(self.born), digits(self.survives))
+    }
+}
+
+// An N-dimensional Life simulation, starting from a 2D slice at all other coordinates 0.
+// Only one representative of each set of cells equivalent under
+// permutation and negation of the higher (beyond x and y) coordinates is stored.
+pub struct Simulation {
+    dimensions: Dim,
+    rounds: Time,
+    // Number of threads to use in each step; 1 means step on the calling thread.
+    // Each step uses no more than there are CPUs or active cells.
+    pub threads: usize,
+    // Life by default. Cells with no neighbours must stay dead,
+    // since only cells next to live ones are considered.
+    pub rule: Rule,
+    time: Time,
+    threads_used: usize,
+    active: Vec<Pos>,
+    weights: CollapsedNeighMap,
+    ybits: usize,
+    wzbits: usize,
+}
+
+impl Simulation {
+    // rounds is the maximum number of rounds that will be simulated;
+    // the bit layout and neighbour weights depend on it.
+    // Panics if the positions won't fit in a Pos; see max_rounds.
+    pub fn new(dimensions: Dim, rounds: Time, initial: &[(usize, usize)]) -> Self {
+        assert!(
+            dimensions >= 3,
+            "need at least 3 dimensions, not {}",
+            dimensions
+        );
+        assert!(
+            layout_bits(dimensions, rounds, initial) <= POS_BITS,
+            "{} rounds in {} dimensions won't fit in a position; at most {:?} will",
+            rounds,
+            dimensions,
+            max_rounds(dimensions, initial)
+        );
+        let max_y = initial.iter().map(|(_, y)| *y).max().unwrap_or(0);
+        let ybits = bit_width(max_y + usize::from(rounds) * 2 + 1);
+        let wzbits = wz_bits(dimensions);
+        let weights = neigh_weights(dimensions, rounds);
+
+        let zs = vec![0; usize::from(dimensions) - 2];
+        let active = initial
+            .iter()
+            .map(|(x, y)| {
+                compress(
+                    Coord::try_from(*x).expect("input too wide"),
+                    Coord::try_from(*y).expect("input too tall"),
+                    &zs,
+                    rounds,
+                    Coord::from(rounds),
+                    ybits,
+                    wzbits,
+                )
+            })
+            .collect();
+
+        Self {
+            dimensions,
+            rounds,
+            threads: 1,
+            rule: Rule::LIFE,
+            time: 0,
+            threads_used: 0,
+            active,
+            weights,
+            ybits,
+            wzbits,
+        }
+    }
+
+    pub fn dimensions(&self) -> Dim {
+        self.dimensions
+    }
+
+    // The most rounds this can simulate; the layout of positions depends on it.
+    pub fn rounds(&self) -> Time {
+        self.rounds
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    // The most threads any step so far has actually used, which may be fewer than threads.
+    pub fn threads_used(&self) -> usize {
+        self.threads_used
+    }
+
+    pub fn step(&mut self) {
+        assert!(
+            self.time < self.rounds,
+            "already simulated all {} rounds",
+            self.rounds
+        );
+        assert!(
+            self.rule.needs_neighbours(),
+            "rule {} changes cells with no neighbours",
+            self.rule
+        );
+        let cpus = std::thread::available_parallelism().map_or(1, usize::from);
+        self.step_with_threads(self.threads.min(cpus).min(self.active.len()));
+    }
+
+    // As step, but without limiting threads, which each keep a map per thread.
+    fn step_with_threads(&mut self, threads: usize) {
+        let threads = threads.max(1);
+        self.threads_used = self.threads_used.max(threads);
+        self.active = if threads > 1 {
+            step_parallel(
+                &self.active,
+                self.rounds,
+                &self.weights,
+                self.ybits,
+                self.wzbits,
+                threads,
+                self.rule,
+            )
+        } else {
+            step(
+                &self.active,
+                self.rounds,
+                &self.weights,
+                self.ybits,
+                self.wzbits,
+                self.rule,
+            )
+        };
+        self.time += 1;
+    }
+
+    // Steps until all rounds have been simulated, returning the final population.
+    pub fn run(&mut self) -> u64 {
+        while self.time < self.rounds {
+            self.step();
+        }
+        self.population()
+    }
+
+    // Steps until the state repeats an earlier one, or until all rounds have been simulated.
+    // Since the state is not translated, only patterns that stay in place are found periodic.
+    pub fn run_until_periodic(&mut self) -> Behaviour {
+        let start = self.time;
+        let mut history = vec![self.canonical_representatives()];
+        let mut seen: HashMap<u64, Vec<Time>> = HashMap::new();
+        seen.insert(hash(&history[0]), vec![self.time]);
+
+        while self.time < self.rounds {
+            self.step();
+            let now = self.canonical_representatives();
+            let times = seen.entry(hash(&now)).or_default();
+            // Compare against the stored states in case of hash collision.
+            if let Some(&first) = times
+                .iter()
+                .find(|&&t| history[usize::from(t - start)] == now)
+            {
+                return Behaviour::Periodic {
+                    first,
+                    period: self.time - first,
+                };
+            }
+            times.push(self.time);
+            history.push(now);
+        }
+
+        Behaviour::Unresolved
+    }
+
+    fn canonical_representatives(&self) -> Vec<Pos> {
+        let mut active = self.active.clone();
+        active.sort_unstable();
+        active
+    }
+
+    // Number of active cells, counting every cell represented by each stored representative.
+    pub fn population(&self) -> u64 {
+        size(&self.active, self.dimensions, self.rounds, self.wzbits)
+    }
+
+    // Compressed representatives of the active cells.
+    pub fn representatives(&self) -> &[Pos] {
+        &self.active
+    }
+
+    // Coordinates of the active representatives,
+    // with higher coordinates nonnegative and in nondecreasing order.
+    pub fn representative_coords(&self) -> Vec<Vec<Coord>> {
+        self.active
+            .iter()
+            .map(|&pos| {
+                decompress(
+                    pos,
+                    self.dimensions,
+                    self.rounds,
+                    Coord::from(self.rounds),
+                    self.ybits,
+                    self.wzbits,
+                )
+            })
+            .collect()
+    }
+
+    // Coordinates of every active cell, not just the representatives.
+    pub fn all_coords(&self) -> Vec<Vec<Coord>> {
+        let mut all = HashSet::new();
+        for rep in self.representative_coords() {
+            let mut higher = rep[2..].to_vec();
+            add_equivalents(&rep, 0, &mut higher, &mut all);
+        }
+        all.into_iter().collect()
+    }
+}
+
+// Adds every arrangement of rep's higher coordinates,
+// in any order and with either sign, keeping the first i of higher as they are.
+fn add_equivalents(rep: &[Coord], i: usize, higher: &mut [Coord], all: &mut HashSet<Vec<Coord>>) {
+    if i == higher.len() {
+        let mut coord = rep[..2].to_vec();
+        coord.extend_from_slice(higher);
+        all.insert(coord);
+        return;
+    }
+    for j in i..higher.len() {
+        higher.swap(i, j);
+        add_equivalents(rep, i + 1, higher, all);
+        if higher[i] != 0 {
+            higher[i] = -higher[i];
+            add_equivalents(rep, i + 1, higher, all);
+            higher[i] = -higher[i];
+        }
+        higher.swap(i, j);
+    }
+}
+
+// Steps initial until the state repeats an earlier one, or until limit rounds have been simulated,
+// returning how it behaved and the final population.
+// Runs a Simulation as far as its layout fits (see max_rounds),
+// then carries on with a NaiveSimulation, which has no such limit but is much slower.
+pub fn find_period(
+    dimensions: Dim,
+    rule: Rule,
+    threads: usize,
+    initial: &[(usize, usize)],
+    limit: Time,
+) -> (Behaviour, u64) {
+    let fast_rounds = max_rounds(dimensions, initial).unwrap_or(0);
+    find_period_switching(dimensions, rule, threads, initial, limit, fast_rounds)
+}
+
+// As find_period, switching to NaiveSimulation after fast_rounds rounds.
+fn find_period_switching(
+    dimensions: Dim,
+    rule: Rule,
+    threads: usize,
+    initial: &[(usize, usize)],
+    limit: Time,
+    fast_rounds: Time,
+) -> (Behaviour, u64) {
+    // Both simulations give their representatives the same coordinates,
+    // so states from either can be compared.
+    let mut seen: HashMap<Vec<Vec<Coord>>, Time> = HashMap::new();
+    let mut check = |time: Time, mut state: Vec<Vec<Coord>>| {
+        state.sort_unstable();
+        seen.insert(state, time).map(|first| Behaviour::Periodic {
+            first,
+            period: time - first,
+        })
+    };
+
+    let mut time = 0;
+    let mut naive = if fast_rounds == 0 {
+        let mut naive = naive::NaiveSimulation::new(dimensions, initial);
+        naive.rule = rule;
+        naive
+    } else {
+        let mut sim = Simulation::new(dimensions, fast_rounds.min(limit), initial);
+        sim.threads = threads;
+        sim.rule = rule;
+        loop {
+            if let Some(behaviour) = check(time, sim.representative_coords()) {
+                return (behaviour, sim.population());
+            }
+            if time == sim.rounds() {
+                break;
+            }
+            sim.step();
+            time += 1;
+        }
+        if time == limit {
+            return (Behaviour::Unresolved, sim.population());
+        }
+        let mut naive = naive::NaiveSimulation::from_cells(dimensions, sim.all_coords());
+        naive.rule = rule;
+        // The state at time has already been checked.
+        naive.step();
+        time += 1;
+        naive
+    };
+
+    loop {
+        if let Some(behaviour) = check(time, naive.representative_coords()) {
+            return (behaviour, naive.population());
+        }
+        if time == limit {
+            return (Behaviour::Unresolved, naive.population());
+        }
+        naive.step();
+        time += 1;
+    }
+}
+
+fn hash(active: &[Pos]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    active.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn step(
+    now_active: &[Pos],
+    rounds: Time,
+    weights: &CollapsedNeighMap,
+    ybits: usize,
+    wzbits: usize,
+    rule: Rule,
+) -> Vec<Pos> {
+    // (neighbour count << 1) | self
+    let mut neigh_and_self: HashMap<Pos, NeighCount> = HashMap::new();
+    add_neighbours(now_active, rounds, weights, ybits, wzbits, |pos, count| {
+        *neigh_and_self.entry(pos).or_insert(0) += count;
+    });
+    for &pos in now_active {
+        // If it was zero, adding 1 won't make a difference.
+        neigh_and_self.entry(pos).and_modify(|e| *e += 1);
+    }
+
+    survivors(neigh_and_self, rule)
+}
+
+// Same as step, but split across threads:
+// each thread counts the neighbours of its share of active cells into one map per shard,
+// then each thread merges all maps for one shard.
+fn step_parallel(
+    now_active: &[Pos],
+    rounds: Time,
+    weights: &CollapsedNeighMap,
+    ybits: usize,
+    wzbits: usize,
+    threads: usize,
+    rule: Rule,
+) -> Vec<Pos> {
+    let shard =
+        |pos: Pos| ((pos as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 32) as usize % threads;
+    let chunk_size = now_active.len().div_ceil(threads).max(1);
+
+    let counted: Vec<Vec<HashMap<Pos, NeighCount>>> = std::thread::scope(|s| {
+        let handles: Vec<_> = now_active
+            .chunks(chunk_size)
+            .map(|chunk| {
+                s.spawn(move || {
+                    let mut shards = vec![HashMap::new(); threads];
+                    add_neighbours(chunk, rounds, weights, ybits, wzbits, |pos, count| {
+                        *shards[shard(pos)].entry(pos).or_insert(0) += count;
+                    });
+                    // Unlike step, self is added even to cells with no neighbours,
+                    // since this thread can't know whether other threads saw neighbours.
+                    // A lone 1 will not survive anyway, since no rule keeps cells with no neighbours.
+                    for &pos in chunk {
+                        *shards[shard(pos)].entry(pos).or_insert(0) += 1;
+                    }
+                    shards
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("step thread panicked"))
+            .collect()
+    });
+
+    let mut by_shard: Vec<Vec<HashMap<Pos, NeighCount>>> = vec![Vec::new(); threads];
+    for shards in counted {
+        for (i, counts) in shards.into_iter().enumerate() {
+            by_shard[i].push(counts);
+        }
+    }
+
+    std::thread::scope(|s| {
+        let handles: Vec<_> = by_shard
+            .into_iter()
+            .map(|maps| {
+                s.spawn(move || {
+                    let mut maps = maps.into_iter();
+                    let mut merged = maps.next().unwrap_or_default();
+                    for counts in maps {
+                        for (pos, count) in counts {
+                            *merged.entry(pos).or_insert(0) += count;
+                        }
+                    }
+                    survivors(merged, rule)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("step thread panicked"))
+            .collect()
+    })
+}
+
+// Calls add(pos, neighbour count << 1) for each neighbour of each active cell.
+fn add_neighbours<F: FnMut(Pos, NeighCount)>(
+    now_active: &[Pos],
+    rounds: Time,
+    weights: &CollapsedNeighMap,
+    ybits: usize,
+    wzbits: usize,
+    mut add: F,
+) {
+    let wzshift = wzbits * (usize::from(rounds) + 1);
+    let wzmask = (1_i64 << wzshift) - 1;
+    let pos_per_dy = 1_i64 << wzshift;
+    let pos_per_dx = pos_per_dy << ybits;
+    let dxys: Vec<_> = repeated_permutation(&[0, -1, 1], 2)
+        .iter()
+        .map(|dxy| dxy[0] * pos_per_dx + dxy[1] * pos_per_dy)
+        .collect();
+
+    for &pos in now_active {
+        for (nwz, weight) in &weights[&(pos & wzmask)] {
+            let npos = pos & !wzmask | nwz;
+            for dxy in &dxys {
+                add(npos + dxy, weight << 1);
+            }
+        }
+        // for e.g. [x, y, z, w] -> [x + 1, y, z, w]
+        // NOTE that if a cell is a representative of one of its own neighbours,
+        // e.g, [x, y, 0, 1] -> [x, y, 1, 0] (which is represented by [x, y, 0, 1]),
+        // the above weights will already have included that fact.
+        // This is only for the single extra neighbour for nonequal [x, y].
+        // TODO: Actually, it'd be possible to include the extra 1 in the neighbour map,
+        // and then subtract the extra neighbour from pos...
+        // You'd think runtime would benefit from avoiding this extra loop,
+        // but it didn't seem to make an actual difference when I tried it?
+        for dxy in &dxys[1..] {
+            add(pos + dxy, 1 << 1);
+        }
+    }
+}
+
+fn survivors(neigh_and_self: HashMap<Pos, NeighCount>, rule: Rule) -> Vec<Pos> {
+    neigh_and_self
+        .into_iter()
+        .filter_map(|(pos, count)| {
+            if rule.next(count & 1 == 1, count >> 1) {
+                Some(pos)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// counting-based representation:
+// x, y, higher_dimensions
+// higher_dimensions is a count of how many of each of 0, 1, 2, ... rounds are present.
+fn compress(
+    x: Coord,
+    y: Coord,
+    wz: &[Time],
+    rounds: Time,
+    xyoffset: Coord,
+    ybits: usize,
+    wzbits: usize,
+) -> Pos {
+    let xy = ((x + xyoffset) << ybits) + y + xyoffset;
+    (Pos::from(xy) << ((usize::from(rounds) + 1) * wzbits))
+        + wz.iter()
+            .map(|&z| 1_i64 << ((z as usize) * wzbits))
+            .sum::<Pos>()
+}
+
+fn decompress(
+    pos: Pos,
+    dimensions: Dim,
+    rounds: Time,
+    xyoffset: Coord,
+    ybits: usize,
+    wzbits: usize,
+) -> Vec<Coord> {
+    let mut pos = pos;
+    let mut coord = vec![0; usize::from(dimensions)];
+    let mut i = 2;
+    for j in 0..=rounds {
+        let n = (pos & ((1 << wzbits) - 1)) as Coord;
+        for _ in 0..n {
+            coord[i] = Coord::from(j);
+            i += 1;
+        }
+        pos >>= wzbits;
+    }
+    // y
+    coord[1] = ((pos & ((1 << ybits) - 1)) as Coord) - xyoffset;
+    // x
+    coord[0] = ((pos >> ybits) as Coord) - xyoffset;
+    coord
+}
+
+// Positions must stay nonnegative.
+const POS_BITS: usize = Pos::BITS as usize - 1;
+
+// How many bits a position takes, packed as compress does,
+// if x and y start within initial and then grow by one each way per round.
+fn layout_bits(dimensions: Dim, rounds: Time, initial: &[(usize, usize)]) -> usize {
+    let span = |max: usize| bit_width(max + usize::from(rounds) * 2 + 1);
+    let max_x = initial.iter().map(|(x, _)| *x).max().unwrap_or(0);
+    let max_y = initial.iter().map(|(_, y)| *y).max().unwrap_or(0);
+    span(max_x) + span(max_y) + wz_bits(dimensions) * (usize::from(rounds) + 1)
+}
+
+// The most rounds a Simulation of initial can be created with,
+// or None if even zero won't fit.
+// The higher dimensions take more bits for each round,
+// so this shrinks as dimensions grow; find_period carries on past it.
+pub fn max_rounds(dimensions: Dim, initial: &[(usize, usize)]) -> Option<Time> {
+    (0..=Time::MAX)
+        .take_while(|&rounds| layout_bits(dimensions, rounds, initial) <= POS_BITS)
+        .last()
+}
+
+pub fn neigh_weights(dimensions: Dim, rounds: Time) -> CollapsedNeighMap {
+    // With no rounds nothing ever steps, so no cell needs its neighbours.
+    if rounds == 0 {
+        return HashMap::new();
+    }
+    let wzbits = wz_bits(dimensions);
+    let mut weights = HashMap::new();
+    // 0 will be first from repeated_permutation, so drop it with [1..]
+    let ds = &(if dimensions <= rounds {
+        repeated_permutation(&[0, -1, 1], usize::from(dimensions - 2))
+    } else {
+        vec![vec![]]
+    })[1..];
+
+    // Recursive closure pattern:
+    // https://stackoverflow.com/questions/16946888/is-it-possible-to-make-a-recursive-closure-in-rust
+    type BuildFn<'s> = dyn Fn(&BuildIfRepresentative, Dim, &mut [Time], &mut NeighMap) + 's;
+    struct BuildIfRepresentative<'s> {
+        f: &'s BuildFn<'s>,
+    }
+    let build_if_representative = BuildIfRepresentative {
+        f: &|build_if_rep, n, prefix, weights| {
+            if n == dimensions - 2 {
+                if dimensions <= rounds {
+                    neigh_weights_by_ds(prefix, ds, rounds, wzbits, weights);
+                } else {
+                    neigh_weights_by_count(prefix, rounds, wzbits, weights);
+                }
+            } else {
+                let last = if n == 0 {
+                    0
+                } else {
+                    prefix[usize::from(n) - 1]
+                };
+                for x in last..=rounds {
+                    prefix[usize::from(n)] = x;
+                    (build_if_rep.f)(build_if_rep, n + 1, prefix, weights);
+                }
+            }
+        },
+    };
+    let mut prefix = vec![0; usize::from(dimensions) - 2];
+    (build_if_representative.f)(&build_if_representative, 0, &mut prefix, &mut weights);
+
+    weights
+        .into_iter()
+        .map(|(k, v)| (k, v.into_iter().collect::<Vec<_>>()))
+        .collect()
+}
+
+fn neigh_weights_by_ds(
+    pt: &[Time],
+    ds: &[Vec<Coord>],
+    rounds: Time,
+    wzbits: usize,
+    h: &mut NeighMap,
+) {
+    assert!(is_representative(pt));
+    let comp_pt = compress(0, 0, pt, rounds, 0, 0, wzbits);
+    for d in ds {
+        let npt: Vec<_> = (0..pt.len()).map(|i| Coord::from(pt[i]) + d[i]).collect();
+        // points with any coordinate equal to # rounds only appear in the last iteration,
+        // so we don't need to compute their outgoing neighbours
+        if npt.iter().any(|n| n.abs() >= Coord::from(rounds)) {
+            continue;
+        }
+        // sorting not needed since counting compression is ordering-invariant
+        let rep: Vec<_> = npt.iter().map(|c| c.unsigned_abs() as Time).collect();
+        let comp_neigh_rep = compress(0, 0, &rep, rounds, 0, 0, wzbits);
+        *h.entry(comp_neigh_rep)
+            .or_default()
+            .entry(comp_pt)
+            .or_insert(0) += 1;
+    }
+}
+
+fn neigh_weights_by_count(pt: &[Time], rounds: Time, wzbits: usize, h: &mut NeighMap) {
+    assert!(is_representative(pt));
+    let mut tally = vec![0; usize::from(rounds) + 1];
+    for &z in pt {
+        tally[usize::from(z)] += 1;
+    }
+    let comp_pt = compress(0, 0, pt, rounds, 0, 0, wzbits);
+    // Recursive closure pattern:
+    // https://stackoverflow.com/questions/16946888/is-it-possible-to-make-a-recursive-closure-in-rust
+    type DecIncFn<'s> =
+        dyn Fn(&DecrementAndIncrement, Time, Pos, NeighCount, Time, Time, bool, &mut NeighMap) + 's;
+    struct DecrementAndIncrement<'s> {
+        f: &'s DecIncFn<'s>,
+    }
+    let dec_and_inc = DecrementAndIncrement {
+        f: &|dec_and_inc, n, comp_so_far, mult, prev_count_minus_dec, prev_inc, all_zero, h| {
+            let count = tally[usize::from(n)];
+            for decrease in 0..=count {
+                let new_comp = comp_so_far
+                    + if n == 0 {
+                        0
+                    } else {
+                        Pos::from(prev_count_minus_dec + decrease)
+                            << ((usize::from(n) - 1) * wzbits)
+                    };
+                if n == rounds - 1 {
+                    // points with any coordinate equal to # rounds only appear in the last iteration,
+                    // so we don't need to compute their outgoing neighbours
+                    // this means we can require that increase from rounds-1 -> rounds be 0,
+                    // and require that decrease from rounds -> rounds-1 be count[rounds]
+                    let decrease_from_above = tally[usize::from(rounds)];
+                    if decrease == 0 && decrease_from_above == 0 && all_zero {
+                        continue;
+                    }
+                    let final_comp = new_comp
+                        + (Pos::from(count - decrease + prev_inc + decrease_from_above)
+                            << (usize::from(n) * wzbits));
+                    *h.entry(final_comp).or_default().entry(comp_pt).or_insert(0) +=
+                        mult * ncr(count, decrease);
+                } else {
+                    for increase in 0..=(count - decrease) {
+                        (dec_and_inc.f)(
+                            dec_and_inc,
+                            n + 1,
+                            new_comp,
+                            mult * ncr(count, decrease) * ncr(count - decrease, increase),
+                            count - decrease - increase + prev_inc,
+                            if n == 0 {
+                                increase + decrease
+                            } else {
+                                increase
+                            },
+                            all_zero && increase == 0 && decrease == 0,
+                            h,
+                        )
+                    }
+                }
+            }
+        },
+    };
+    (dec_and_inc.f)(&dec_and_inc, 0, 0, 1, 0, 0, true, h);
+}
+
+fn is_representative(pt: &[Time]) -> bool {
+    for i in 1..pt.len() {
+        if pt[i - 1] > pt[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn size(compressed: &[Pos], dimensions: Dim, rounds: Time, wzbits: usize) -> u64 {
+    let perms_wz: u64 = (1..=u64::from(dimensions - 2)).product();
+    compressed
+        .iter()
+        .map(|pos| {
+            let mut count = 1_u64;
+            let mut perms_pos = 1_u64;
+            for i in 0..=rounds {
+                let shifted = pos >> (wzbits * usize::from(i));
+                let count_of_i = shifted & ((1 << wzbits) - 1);
+                if i != 0 {
+                    count <<= count_of_i;
+                }
+                perms_pos *= (1_u64..=(count_of_i as u64)).product::<u64>();
+            }
+            count * perms_wz / perms_pos
+        })
+        .sum()
+}
+
+// Probably would be better represented as an iterator,
+// but it's called just once so I don't really care.
+fn repeated_permutation<T: Copy>(xs: &[T], n: usize) -> Vec<Vec<T>> {
+    let mut vs = vec![vec![]];
+    let mut n = n;
+    while n > 0 {
+        let mut vs_with = vec![];
+        for x in xs {
+            for v_without in &vs {
+                let mut v_with = v_without.clone();
+                v_with.push(*x);
+                vs_with.push(v_with);
+            }
+        }
+        vs = vs_with;
+        n -= 1;
+    }
+    vs
+}
+
+fn ncr(n: Time, k: Time) -> NeighCount {
+    let mut r = 1;
+    for i in 0..NeighCount::from(k) {
+        // the division is safe and won't truncate a fraction.
+        // division by j only happens when r has been multiplied by j numbers,
+        // at least one of which must have been a multiple of j.
+        r = r * (NeighCount::from(n) - i) / (i + 1);
+    }
+    r
+}
+
+fn wz_bits(dimensions: Dim) -> usize {
+    bit_width(usize::from(dimensions) - 2)
+}
+
+fn bit_width(n: usize) -> usize {
+    let mut w = 0;
+    let mut n = n;
+    while n > 0 {
+        w += 1;
+        n >>= 1;
+    }
+    w
+}
+
+pub fn active(s: &str) -> crate::Result<Vec<(usize, usize)>> {
+    let grid = Grid::parse(s, hash_or_dot)?;
+    Ok(grid.positions(|&active| active).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::naive::NaiveSimulation;
+    use super::*;
+
+    const GLIDER: &str = ".#.\n..#\n###\n";
+
+    fn random_grid(seed: u64, width: usize, height: usize) -> Vec<(usize, usize)> {
+        // xorshift64; seeds must be nonzero.
+        let mut state = seed;
+        let mut cells = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                if state.is_multiple_of(3) {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+
+    fn sorted(mut coords: Vec<Vec<Coord>>) -> Vec<Vec<Coord>> {
+        coords.sort_unstable();
+        coords
+    }
+
+    fn compare_to_naive(dimensions: Dim, rounds: Time, initial: &[(usize, usize)]) {
+        compare_to_naive_with(Rule::LIFE, dimensions, rounds, initial)
+    }
+
+    fn compare_to_naive_with(
+        rule: Rule,
+        dimensions: Dim,
+        rounds: Time,
+        initial: &[(usize, usize)],
+    ) {
+        let mut sim = Simulation::new(dimensions, rounds, initial);
+        let mut naive = NaiveSimulation::new(dimensions, initial);
+        sim.rule = rule;
+        naive.rule = rule;
+        for t in 0..=rounds {
+            if t > 0 {
+                sim.step();
+                naive.step();
+            }
+            assert_eq!(
+                sim.population(),
+                naive.population(),
+                "population at t={} of {} rounds, {} dimensions",
+                t,
+                rounds,
+                dimensions
+            );
+            assert_eq!(
+                sorted(sim.representative_coords()),
+                sorted(naive.representative_coords()),
+                "representatives at t={} of {} rounds, {} dimensions",
+                t,
+                rounds,
+                dimensions
+            );
+        }
+    }
+
+    #[test]
+    fn glider_matches_naive() {
+        let initial = active(GLIDER).unwrap();
+        for dimensions in 3..=5 {
+            for rounds in 1..=4 {
+                compare_to_naive(dimensions, rounds, &initial);
+            }
+        }
+        for rounds in 1..=3 {
+            compare_to_naive(6, rounds, &initial);
+        }
+    }
+
+    #[test]
+    fn random_grids_match_naive() {
+        for seed in 1_u64..=4 {
+            let initial = random_grid(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15), 5, 4);
+            for dimensions in 3..=5 {
+                for rounds in 1..=3 {
+                    compare_to_naive(dimensions, rounds, &initial);
+                }
+            }
+            compare_to_naive(6, 2, &initial);
+        }
+    }
+
+    #[test]
+    fn other_rules_match_naive() {
+        let initial = random_grid(0x2545_f491_4f6c_dd1d, 5, 5);
+        for rule in ["B36/S23", "B2/S", "B3/S12345678"] {
+            let rule = rule.parse().unwrap();
+            for dimensions in 3..=4 {
+                compare_to_naive_with(rule, dimensions, 3, &initial);
+            }
+        }
+    }
+
+    #[test]
+    fn rules() {
+        let life: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(life, Rule::LIFE);
+        assert_eq!(life.to_string(), "B3/S23");
+        assert!(life.next(false, 3) && !life.next(false, 2));
+        assert!(life.next(true, 2) && !life.next(true, 4));
+        assert!(!life.next(true, 100));
+        assert_eq!(Rule::new(&[0], &[]).to_string(), "B0/S");
+        for invalid in ["B3S23", "3/S23", "B3/23", "B3/Sx"] {
+            assert!(invalid.parse::<Rule>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "only 0 to 9")]
+    fn rule_count_too_high() {
+        Rule::new(&[12], &[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no neighbours")]
+    fn rule_born_from_nothing() {
+        let mut sim = Simulation::new(3, 1, &[(0, 0)]);
+        sim.rule = Rule::new(&[0, 3], &[2, 3]);
+        sim.step();
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        let initial = random_grid(0x2545_f491_4f6c_dd1d, 8, 8);
+        for dimensions in 3..=6 {
+            for threads in 2..=5 {
+                let mut serial = Simulation::new(dimensions, 4, &initial);
+                let mut parallel = Simulation::new(dimensions, 4, &initial);
+                // Not Life, so that the rule is seen to reach every thread.
+                serial.rule = "B36/S23".parse().unwrap();
+                parallel.rule = serial.rule;
+                for t in 1..=4 {
+                    serial.step();
+                    // Unlimited, since this machine may have fewer CPUs than threads.
+                    parallel.step_with_threads(threads);
+                    assert_eq!(
+                        sorted(serial.representative_coords()),
+                        sorted(parallel.representative_coords()),
+                        "{} dimensions, {} threads at t={}",
+                        dimensions,
+                        threads,
+                        t
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn threads_are_limited() {
+        // One active cell, so one thread, however many are asked for.
+        let mut sim = Simulation::new(3, 2, &[(0, 0)]);
+        sim.threads = 10_000;
+        sim.step();
+        assert_eq!(sim.population(), 0);
+        assert_eq!(sim.threads_used(), 1);
+    }
+
+    #[test]
+    fn block_is_still_life() {
+        let initial = active("##\n##\n").unwrap();
+        for dimensions in 3..=5 {
+            let mut sim = Simulation::new(dimensions, 6, &initial);
+            assert_eq!(
+                sim.run_until_periodic(),
+                Behaviour::Periodic {
+                    first: 0,
+                    period: 1
+                }
+            );
+            assert_eq!(sim.population(), 4);
+        }
+    }
+
+    #[test]
+    fn dying_pattern_is_periodic_once_empty() {
+        let mut sim = Simulation::new(3, 6, &active("#\n").unwrap());
+        assert_eq!(
+            sim.run_until_periodic(),
+            Behaviour::Periodic {
+                first: 1,
+                period: 1
+            }
+        );
+        assert_eq!(sim.population(), 0);
+    }
+
+    #[test]
+    fn growing_glider_is_unresolved() {
+        let mut sim = Simulation::new(3, 6, &active(GLIDER).unwrap());
+        assert_eq!(sim.run_until_periodic(), Behaviour::Unresolved);
+        assert_eq!(sim.time(), 6);
+        assert_eq!(sim.population(), 112);
+    }
+
+    #[test]
+    fn zero_rounds() {
+        let initial = active(GLIDER).unwrap();
+        for dimensions in 3..=5 {
+            let mut sim = Simulation::new(dimensions, 0, &initial);
+            assert_eq!(sim.run(), 5);
+            assert_eq!(
+                sim.run_until_periodic(),
+                Behaviour::Unresolved,
+                "{} dimensions",
+                dimensions
+            );
+            compare_to_naive(dimensions, 0, &initial);
+        }
+    }
+
+    #[test]
+    fn all_coords_carry_on_naively() {
+        let initial = active(GLIDER).unwrap();
+        for dimensions in 3..=5 {
+            let mut sim = Simulation::new(dimensions, 6, &initial);
+            for _ in 0..3 {
+                sim.step();
+            }
+            let all = sim.all_coords();
+            assert_eq!(
+                all.len() as u64,
+                sim.population(),
+                "{} dimensions",
+                dimensions
+            );
+            let mut naive = NaiveSimulation::from_cells(dimensions, all);
+            for _ in 0..3 {
+                sim.step();
+                naive.step();
+            }
+            assert_eq!(
+                sorted(sim.representative_coords()),
+                sorted(naive.representative_coords()),
+                "{} dimensions",
+                dimensions
+            );
+        }
+    }
+
+    fn switching_agrees(input: &str, dimensions: Dim, limit: Time) {
+        let initial = active(input).unwrap();
+        let fast = find_period_switching(dimensions, Rule::LIFE, 1, &initial, limit, limit);
+        for fast_rounds in 0..limit {
+            assert_eq!(
+                find_period_switching(dimensions, Rule::LIFE, 1, &initial, limit, fast_rounds),
+                fast,
+                "switching after {} rounds",
+                fast_rounds
+            );
+        }
+    }
+
+    #[test]
+    fn find_period_switching_agrees() {
+        switching_agrees(GLIDER, 3, 6);
+        switching_agrees("##\n##\n", 3, 3);
+        // Dies out after 3 rounds.
+        switching_agrees("##\n#.\n", 3, 5);
+        switching_agrees("#\n", 4, 3);
+    }
+
+    #[test]
+    fn block_is_still_life_at_max_rounds() {
+        let initial = active("##\n##\n").unwrap();
+        for dimensions in 3..=5 {
+            let rounds = max_rounds(dimensions, &initial).unwrap();
+            let mut sim = Simulation::new(dimensions, rounds, &initial);
+            assert_eq!(
+                sim.run_until_periodic(),
+                Behaviour::Periodic {
+                    first: 0,
+                    period: 1
+                }
+            );
+            assert_eq!(sim.population(), 4, "{} dimensions", dimensions);
+        }
+    }
+
+    #[test]
+    fn glider_matches_naive_at_max_rounds() {
+        let initial = active(GLIDER).unwrap();
+        for dimensions in 3..=4 {
+            // The layout for the most rounds, but only some of them, to keep the naive one quick.
+            let rounds = max_rounds(dimensions, &initial).unwrap();
+            let mut sim = Simulation::new(dimensions, rounds, &initial);
+            let mut naive = NaiveSimulation::new(dimensions, &initial);
+            for _ in 0..8 {
+                sim.step();
+                naive.step();
+            }
+            assert_eq!(
+                sorted(sim.representative_coords()),
+                sorted(naive.representative_coords()),
+                "{} dimensions",
+                dimensions
+            );
+        }
+    }
+
+    #[test]
+    fn max_rounds_example() {
+        let initial = active(GLIDER).unwrap();
+        assert_eq!(max_rounds(3, &initial), Some(48));
+        assert_eq!(max_rounds(4, &initial), Some(24));
+    }
+
+    #[test]
+    #[should_panic(expected = "won't fit")]
+    fn too_many_rounds() {
+        Simulation::new(3, 100, &active(GLIDER).unwrap());
+    }
+
+    #[test]
+    fn glider_example() {
+        let initial = active(GLIDER).unwrap();
+        assert_eq!(Simulation::new(3, 6, &initial).run(), 112);
+        assert_eq!(Simulation::new(4, 6, &initial).run(), 848);
+    }
+}
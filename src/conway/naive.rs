@@ -0,0 +1,99 @@
+// Straightforward reference implementation, storing every active cell.
+// Much slower than Simulation, but with no cleverness to get wrong.
+
+use super::{Coord, Dim, Rule};
+use std::collections::{HashMap, HashSet};
+
+pub struct NaiveSimulation {
+    pub dimensions: Dim,
+    pub rule: Rule,
+    active: HashSet<Vec<Coord>>,
+}
+
+impl NaiveSimulation {
+    pub fn new(dimensions: Dim, initial: &[(usize, usize)]) -> Self {
+        let active = initial
+            .iter()
+            .map(|&(x, y)| {
+                let mut coord = vec![0; usize::from(dimensions)];
+                coord[0] = Coord::try_from(x).expect("input too wide");
+                coord[1] = Coord::try_from(y).expect("input too tall");
+                coord
+            })
+            .collect();
+        Self {
+            dimensions,
+            rule: Rule::LIFE,
+            active,
+        }
+    }
+
+    // Starts from the given active cells, which each have dimensions coordinates.
+    pub fn from_cells<I: IntoIterator<Item = Vec<Coord>>>(dimensions: Dim, cells: I) -> Self {
+        let active: HashSet<_> = cells.into_iter().collect();
+        assert!(
+            active
+                .iter()
+                .all(|pos| pos.len() == usize::from(dimensions)),
+            "cells must have {} coordinates",
+            dimensions
+        );
+        Self {
+            dimensions,
+            rule: Rule::LIFE,
+            active,
+        }
+    }
+
+    pub fn step(&mut self) {
+        let mut neigh: HashMap<Vec<Coord>, u32> = HashMap::new();
+        for pos in &self.active {
+            let mut npos = pos.clone();
+            add_neighbours(pos, 0, &mut npos, &mut neigh);
+        }
+        self.active = neigh
+            .into_iter()
+            .filter_map(|(pos, count)| {
+                if self.rule.next(self.active.contains(&pos), count) {
+                    Some(pos)
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    pub fn population(&self) -> u64 {
+        self.active.len() as u64
+    }
+
+    // Active cells whose higher coordinates are nonnegative and in nondecreasing order,
+    // which are the cells Simulation keeps as representatives.
+    pub fn representative_coords(&self) -> Vec<Vec<Coord>> {
+        self.active
+            .iter()
+            .filter(|pos| {
+                pos[2..].windows(2).all(|w| w[0] <= w[1]) && pos[2..].iter().all(|&c| c >= 0)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+fn add_neighbours(
+    pos: &[Coord],
+    i: usize,
+    npos: &mut Vec<Coord>,
+    neigh: &mut HashMap<Vec<Coord>, u32>,
+) {
+    if i == pos.len() {
+        if npos != pos {
+            *neigh.entry(npos.clone()).or_insert(0) += 1;
+        }
+        return;
+    }
+    for d in -1..=1 {
+        npos[i] = pos[i] + d;
+        add_neighbours(pos, i + 1, npos, neigh);
+    }
+}
@@ -1,10 +1,67 @@
+use crate::solution::Solution;
 use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Sub;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Report Repair";
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> crate::Result<Vec<u32>> {
+        crate::parse_lines(input, crate::parse)
+    }
+
+    fn part1(nums: &Vec<u32>) -> crate::Result<String> {
+        first(pair(nums))
+    }
+
+    fn part2(nums: &Vec<u32>) -> crate::Result<String> {
+        first(by_combinations_sorted(
+            nums,
+            &nums.iter().cloned().collect(),
+        ))
+    }
+}
+
+fn first(ans: Vec<u32>) -> crate::Result<String> {
+    ans.first()
+        .map(u32::to_string)
+        .ok_or_else(|| crate::Error::invalid("no entries sum to 2020"))
+}
+
+// Products of each pair of entries that sum to 2020.
+pub fn pair(nums: &[u32]) -> Vec<u32> {
+    pairs_summing_to(nums, 2020).map(|(a, b)| a * b).collect()
+}
+
+// Each pair of entries, at different positions, that sum to target, the smaller first.
+// Pairs are found in order of where their later entry is.
+pub fn pairs_summing_to<T>(nums: &[T], target: T) -> impl Iterator<Item = (T, T)> + '_
+where
+    T: Copy + Ord + Hash + Sub<Output = T>,
+{
+    let mut seen = HashSet::with_capacity(nums.len());
+    nums.iter().filter_map(move |&num| {
+        let found = (num <= target)
+            .then(|| target - num)
+            .filter(|needed| seen.contains(needed))
+            .map(|needed| (needed.min(num), needed.max(num)));
+        seen.insert(num);
+        found
+    })
+}
 
 pub fn by_combinations(nums: &[u32], nums_set: &HashSet<u32>) -> Vec<u32> {
     let mut ans = Vec::new();
     for (i, x) in nums.iter().enumerate() {
         for &y in nums.iter().skip(i + 1) {
-            let needed = 2020 - x - y;
+            let needed = match 2020_u32.checked_sub(x + y) {
+                Some(needed) => needed,
+                None => continue,
+            };
             if needed < *x || needed < y {
                 continue;
             }
@@ -28,7 +85,10 @@ pub fn by_combinations_sorted(nums: &[u32], nums_set: &HashSet<u32>) -> Vec<u32>
             break;
         }
         for &y in sorted.iter().skip(i + 1) {
-            let needed = 2020 - x - y;
+            let needed = match 2020_u32.checked_sub(x + y) {
+                Some(needed) => needed,
+                None => break,
+            };
             if needed < y {
                 break;
             }
@@ -69,6 +129,9 @@ pub fn over_input_range(nums: &[u32], nums_set: &HashSet<u32>) -> Vec<u32> {
         }
 
         for b in a..=max {
+            if a + b > 2020 {
+                break;
+            }
             let c = 2020 - a - b;
             if c < b {
                 break;
@@ -80,3 +143,52 @@ pub fn over_input_range(nums: &[u32], nums_set: &HashSet<u32>) -> Vec<u32> {
     }
     ans
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day01>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day01>(input, expected)
+    }
+
+    fn all_agree(input: &str) {
+        let nums = Day01::parse(input).unwrap();
+        let nums_set = nums.iter().cloned().collect();
+        let expected = by_combinations(&nums, &nums_set);
+        assert_eq!(over_input_range(&nums, &nums_set), expected);
+        assert_eq!(by_combinations_sorted(&nums, &nums_set), expected);
+    }
+
+    fn pairs(nums: &[u32], target: u32, expected: &[(u32, u32)]) {
+        let found: Vec<_> = pairs_summing_to(nums, target).collect();
+        assert_eq!(found, expected);
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/01.in"), "514579");
+        }
+        pairs {
+            one_pair(&[1721, 979, 366, 299, 675, 1456], 2020, &[(299, 1721)]);
+            half_once(&[1010, 5], 2020, &[]);
+            half_twice(&[1010, 5, 1010], 2020, &[(1010, 1010)]);
+            above_target(&[3000, 1, 2], 3, &[(1, 2)]);
+            several(&[1, 4, 2, 3], 5, &[(1, 4), (2, 3)]);
+        }
+        part2 {
+            part2_example(include_str!("../cases/01.in"), "241861950");
+        }
+        all_agree {
+            methods_agree_on_example(include_str!("../cases/01.in"));
+            methods_agree_above_target("1\n2500\n");
+            methods_agree_with_large_entry("1721\n979\n366\n299\n675\n1456\n2500\n");
+        }
+    }
+}
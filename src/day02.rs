@@ -0,0 +1,138 @@
+use crate::solution::Solution;
+use crate::{Error, NumberMode};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const NAME: &'static str = "Password Philosophy";
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> crate::Result<Vec<Entry>> {
+        crate::parse_lines(input, str::parse)
+    }
+
+    fn part1(entries: &Vec<Entry>) -> crate::Result<String> {
+        Ok(count_valid(entries, &CountRange).to_string())
+    }
+
+    fn part2(entries: &Vec<Entry>) -> crate::Result<String> {
+        Ok(count_valid(entries, &Positions).to_string())
+    }
+}
+
+// One line of the database: a policy's two numbers and letter, and the password.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub a: usize,
+    pub b: usize,
+    pub letter: u8,
+    pub password: Vec<u8>,
+}
+
+impl std::str::FromStr for Entry {
+    type Err = Error;
+
+    // 1-3 a: abcde
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let (policy, password) = s
+            .split_once(": ")
+            .ok_or_else(|| Error::parse(s, "expected policy: password"))?;
+        let (range, letter) = policy
+            .rsplit_once(' ')
+            .ok_or_else(|| Error::parse(policy, "expected a range and a letter"))?;
+        let letter = match letter.as_bytes() {
+            &[c] => c,
+            _ => return Err(Error::parse(letter, "expected a single letter")),
+        };
+        let (a, b) = match crate::numbers_with::<usize>(range, NumberMode::Unsigned)?[..] {
+            [a, b] => (a, b),
+            _ => return Err(Error::parse(range, "expected two numbers")),
+        };
+        Ok(Entry {
+            a,
+            b,
+            letter,
+            password: password.as_bytes().to_vec(),
+        })
+    }
+}
+
+pub trait PasswordPolicy {
+    fn is_valid(&self, entry: &Entry) -> bool;
+}
+
+// The letter appears between a and b times, inclusive.
+pub struct CountRange;
+
+impl PasswordPolicy for CountRange {
+    fn is_valid(&self, entry: &Entry) -> bool {
+        let count = entry
+            .password
+            .iter()
+            .filter(|&&c| c == entry.letter)
+            .count();
+        (entry.a..=entry.b).contains(&count)
+    }
+}
+
+// The letter is at exactly one of positions a and b, counting from 1.
+pub struct Positions;
+
+impl PasswordPolicy for Positions {
+    fn is_valid(&self, entry: &Entry) -> bool {
+        let at = |i: usize| i > 0 && entry.password.get(i - 1) == Some(&entry.letter);
+        at(entry.a) != at(entry.b)
+    }
+}
+
+pub fn count_valid<P: PasswordPolicy + ?Sized>(entries: &[Entry], policy: &P) -> usize {
+    entries.iter().filter(|e| policy.is_valid(e)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day02>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day02>(input, expected)
+    }
+
+    fn valid(line: &str, count_range: bool, positions: bool) {
+        let entry: Entry = line.parse().unwrap();
+        assert_eq!(CountRange.is_valid(&entry), count_range, "count range");
+        assert_eq!(Positions.is_valid(&entry), positions, "positions");
+    }
+
+    fn invalid_line(line: &str) {
+        assert!(line.parse::<Entry>().is_err());
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/02.in"), "2");
+        }
+        part2 {
+            part2_example(include_str!("../cases/02.in"), "1");
+        }
+        valid {
+            example1("1-3 a: abcde", true, true);
+            example2("1-3 b: cdefg", false, false);
+            example3("2-9 c: ccccccccc", true, false);
+            both_positions("1-2 a: aab", true, false);
+            past_end("1-10 a: ab", true, true);
+            multi_digit("10-12 z: zzzzzzzzzzzz", true, false);
+        }
+        invalid_line {
+            no_colon("1-3 a abcde");
+            one_number("3 a: abcde");
+            long_letter("1-3 ab: abcde");
+        }
+    }
+}
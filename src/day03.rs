@@ -0,0 +1,80 @@
+use crate::grid::{hash_or_dot, Grid};
+use crate::solution::Solution;
+
+pub struct Day03;
+
+pub const PART1_SLOPES: &[Slope] = &[(3, 1)];
+pub const PART2_SLOPES: &[Slope] = &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Toboggan Trajectory";
+    type Input = Grid<bool>;
+
+    fn parse(input: &str) -> crate::Result<Grid<bool>> {
+        Grid::parse(input, hash_or_dot)
+    }
+
+    fn part1(trees: &Grid<bool>) -> crate::Result<String> {
+        Ok(trees_on_slopes(trees, PART1_SLOPES).1.to_string())
+    }
+
+    fn part2(trees: &Grid<bool>) -> crate::Result<String> {
+        Ok(trees_on_slopes(trees, PART2_SLOPES).1.to_string())
+    }
+}
+
+// (right, down)
+pub type Slope = (usize, usize);
+
+// Trees hit going from the top left to the bottom, wrapping around horizontally.
+pub fn trees_on_slope(trees: &Grid<bool>, (right, down): Slope) -> usize {
+    assert!(down > 0, "slope must go down");
+    (0..trees.height())
+        .step_by(down)
+        .enumerate()
+        .filter(|&(i, y)| *trees.get_wrapping((i * right) as isize, y as isize))
+        .count()
+}
+
+// The count for each slope, and the product of all counts.
+pub fn trees_on_slopes(trees: &Grid<bool>, slopes: &[Slope]) -> (Vec<usize>, u64) {
+    let counts: Vec<_> = slopes.iter().map(|&s| trees_on_slope(trees, s)).collect();
+    let product = counts.iter().map(|&c| c as u64).product();
+    (counts, product)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day03>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day03>(input, expected)
+    }
+
+    fn slopes(input: &str, slopes: &[Slope], counts: &[usize], product: u64) {
+        let trees = Day03::parse(input).unwrap();
+        assert_eq!(trees_on_slopes(&trees, slopes), (counts.to_vec(), product));
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/03.in"), "7");
+        }
+        part2 {
+            part2_example(include_str!("../cases/03.in"), "336");
+        }
+        slopes {
+            five_slopes(include_str!("../cases/03.in"), PART2_SLOPES, &[2, 7, 3, 4, 2], 336);
+            no_slopes(include_str!("../cases/03.in"), &[], &[], 1);
+            straight_down(include_str!("../cases/03.in"), &[(0, 1)], &[3], 3);
+            steep(include_str!("../cases/03.in"), &[(1, 3)], &[0], 0);
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use crate::sections::{paragraphs, Paragraph};
+use crate::solution::Solution;
+use crate::Error;
+use std::fmt;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const NAME: &'static str = "Passport Processing";
+    type Input = Vec<Passport>;
+
+    fn parse(input: &str) -> crate::Result<Vec<Passport>> {
+        paragraphs(input)
+            .iter()
+            .map(|p| Passport::parse(p).map_err(|e| e.at_line(p.first_line())))
+            .collect()
+    }
+
+    fn part1(passports: &Vec<Passport>) -> crate::Result<String> {
+        let present = passports.iter().filter(|p| p.missing().is_none());
+        Ok(present.count().to_string())
+    }
+
+    fn part2(passports: &Vec<Passport>) -> crate::Result<String> {
+        let valid = passports.iter().filter(|p| p.problem().is_none());
+        Ok(valid.count().to_string())
+    }
+}
+
+// What a field's value must look like.
+#[derive(Clone, Copy, Debug)]
+pub enum Rule {
+    // Four digits, as a year within min..=max.
+    Year { min: u32, max: u32 },
+    // A number directly followed by one of the units, within that unit's range.
+    Measure(&'static [(&'static str, u32, u32)]),
+    // # followed by exactly this many lowercase hex digits.
+    Hex(usize),
+    OneOf(&'static [&'static str]),
+    // Exactly this many digits, leading zeroes included.
+    Digits(usize),
+}
+
+impl Rule {
+    // Why the value breaks the rule, if it does.
+    pub fn check(&self, value: &str) -> Option<String> {
+        let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        match *self {
+            Rule::Year { min, max } => {
+                if value.len() != 4 || !all_digits(value) {
+                    return Some("not a four-digit year".to_string());
+                }
+                within(value, min, max)
+            }
+            Rule::Measure(units) => {
+                let split = value.find(|c: char| !c.is_ascii_digit());
+                let (n, unit) = value.split_at(split.unwrap_or(value.len()));
+                if !all_digits(n) {
+                    return Some("no number".to_string());
+                }
+                match units.iter().find(|(u, _, _)| *u == unit) {
+                    Some(&(_, min, max)) => within(n, min, max),
+                    None => {
+                        let names: Vec<_> = units.iter().map(|(u, _, _)| *u).collect();
+                        Some(format!("unit is not one of {}", names.join(", ")))
+                    }
+                }
+            }
+            Rule::Hex(len) => match value.strip_prefix('#') {
+                Some(hex)
+                    if hex.len() == len
+                        && hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) =>
+                {
+                    None
+                }
+                _ => Some(format!("not # and {} lowercase hex digits", len)),
+            },
+            Rule::OneOf(allowed) => {
+                (!allowed.contains(&value)).then(|| format!("not one of {}", allowed.join(", ")))
+            }
+            Rule::Digits(len) => {
+                (value.len() != len || !all_digits(value)).then(|| format!("not {} digits", len))
+            }
+        }
+    }
+}
+
+fn within(n: &str, min: u32, max: u32) -> Option<String> {
+    match n.parse::<u32>() {
+        Ok(n) if (min..=max).contains(&n) => None,
+        _ => Some(format!("not between {} and {}", min, max)),
+    }
+}
+
+pub struct Field {
+    pub name: &'static str,
+    pub rule: Rule,
+}
+
+// The required fields, in the order they are checked. cid is optional, so isn't here.
+pub const FIELDS: &[Field] = &[
+    Field {
+        name: "byr",
+        rule: Rule::Year {
+            min: 1920,
+            max: 2002,
+        },
+    },
+    Field {
+        name: "iyr",
+        rule: Rule::Year {
+            min: 2010,
+            max: 2020,
+        },
+    },
+    Field {
+        name: "eyr",
+        rule: Rule::Year {
+            min: 2020,
+            max: 2030,
+        },
+    },
+    Field {
+        name: "hgt",
+        rule: Rule::Measure(&[("cm", 150, 193), ("in", 59, 76)]),
+    },
+    Field {
+        name: "hcl",
+        rule: Rule::Hex(6),
+    },
+    Field {
+        name: "ecl",
+        rule: Rule::OneOf(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]),
+    },
+    Field {
+        name: "pid",
+        rule: Rule::Digits(9),
+    },
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    Missing(&'static str),
+    Invalid {
+        field: &'static str,
+        value: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Missing(field) => write!(f, "{} missing", field),
+            Problem::Invalid {
+                field,
+                value,
+                reason,
+            } => write!(f, "{} {}: {}", field, value, reason),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Passport {
+    // Counting from 1; where the passport starts in the input.
+    pub line: usize,
+    pub fields: Vec<(String, String)>,
+}
+
+impl Passport {
+    // key:value pairs separated by spaces or newlines. Keys may not repeat.
+    pub fn parse(paragraph: &Paragraph) -> crate::Result<Self> {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        for line in &paragraph.lines {
+            for pair in line.text.split_whitespace() {
+                let err = |msg| Error::parse(pair, msg).at_line(line.number);
+                let (k, v) = pair
+                    .split_once(':')
+                    .ok_or_else(|| err("expected key:value"))?;
+                if fields.iter().any(|(key, _)| key == k) {
+                    return Err(err("repeated key"));
+                }
+                fields.push((k.to_string(), v.to_string()));
+            }
+        }
+        Ok(Passport {
+            line: paragraph.first_line(),
+            fields,
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    // The first required field that's absent.
+    pub fn missing(&self) -> Option<Problem> {
+        FIELDS
+            .iter()
+            .find(|f| self.get(f.name).is_none())
+            .map(|f| Problem::Missing(f.name))
+    }
+
+    // The first required field that's absent or breaks its rule.
+    pub fn problem(&self) -> Option<Problem> {
+        FIELDS.iter().find_map(|f| match self.get(f.name) {
+            None => Some(Problem::Missing(f.name)),
+            Some(value) => f.rule.check(value).map(|reason| Problem::Invalid {
+                field: f.name,
+                value: value.to_string(),
+                reason,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day04>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day04>(input, expected)
+    }
+
+    fn field(name: &str, value: &str, valid: bool) {
+        let f = FIELDS.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(
+            f.rule.check(value).is_none(),
+            valid,
+            "{:?}",
+            f.rule.check(value)
+        );
+    }
+
+    fn problem(input: &str, expected: &str) {
+        let passports = Day04::parse(input).unwrap();
+        assert_eq!(passports[0].problem().unwrap().to_string(), expected);
+    }
+
+    fn invalid_input(input: &str, line: usize) {
+        assert_eq!(Day04::parse(input).unwrap_err().line, Some(line));
+    }
+
+    const INVALID: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+";
+
+    const VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
+";
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/04.in"), "2");
+        }
+        part2 {
+            part2_example(include_str!("../cases/04.in"), "2");
+            part2_invalid(INVALID, "0");
+            part2_valid(VALID, "4");
+        }
+        field {
+            byr_valid("byr", "2002", true);
+            byr_high("byr", "2003", false);
+            byr_short("byr", "200", false);
+            hgt_in("hgt", "60in", true);
+            hgt_cm("hgt", "190cm", true);
+            hgt_in_high("hgt", "190in", false);
+            hgt_no_unit("hgt", "190", false);
+            hgt_no_number("hgt", "cm", false);
+            hcl_valid("hcl", "#123abc", true);
+            hcl_bad_digit("hcl", "#123abz", false);
+            hcl_no_hash("hcl", "123abc", false);
+            hcl_upper("hcl", "#123ABC", false);
+            ecl_valid("ecl", "brn", true);
+            ecl_invalid("ecl", "wat", false);
+            pid_valid("pid", "000000001", true);
+            pid_long("pid", "0123456789", false);
+            pid_sign("pid", "+12345678", false);
+        }
+        problem {
+            missing("ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\niyr:2017 hgt:183cm", "byr missing");
+            bad_year("byr:1900 iyr:2017 eyr:2020 hgt:183cm", "byr 1900: not between 1920 and 2002");
+            bad_unit("byr:1937 iyr:2017 eyr:2020 hgt:183", "hgt 183: unit is not one of cm, in");
+        }
+        invalid_input {
+            no_colon("byr:1937\n\niyr:2017 eyr", 3);
+            repeated("byr:1937\nbyr:1938", 2);
+        }
+    }
+}
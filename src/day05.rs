@@ -0,0 +1,127 @@
+use crate::grid::Grid;
+use crate::solution::Solution;
+use crate::Error;
+
+pub struct Day05;
+
+pub const ROWS: usize = 128;
+pub const COLUMNS: usize = 8;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const NAME: &'static str = "Binary Boarding";
+    type Input = Vec<u16>;
+
+    fn parse(input: &str) -> crate::Result<Vec<u16>> {
+        crate::parse_lines(input, seat_id)
+    }
+
+    fn part1(ids: &Vec<u16>) -> crate::Result<String> {
+        ids.iter()
+            .max()
+            .map(u16::to_string)
+            .ok_or_else(|| Error::invalid("no boarding passes"))
+    }
+
+    fn part2(ids: &Vec<u16>) -> crate::Result<String> {
+        missing_seat(ids)
+            .map(|id| id.to_string())
+            .ok_or_else(|| Error::invalid("no empty seat between two taken ones"))
+    }
+}
+
+// Row and column are just the high and low bits of the ID,
+// with F and L as 0 and B and R as 1.
+pub fn seat_id(pass: &str) -> crate::Result<u16> {
+    if pass.len() != 10 {
+        return Err(Error::parse(pass, "expected 10 characters"));
+    }
+    pass.bytes().enumerate().try_fold(0, |id, (i, c)| {
+        let bit = match (i < 7, c) {
+            (true, b'F') | (false, b'L') => 0,
+            (true, b'B') | (false, b'R') => 1,
+            (true, _) => return Err(Error::parse(pass, "expected F or B").at_column(i + 1)),
+            (false, _) => return Err(Error::parse(pass, "expected L or R").at_column(i + 1)),
+        };
+        Ok(id << 1 | bit)
+    })
+}
+
+// Which seats are taken: one row of the grid per row of the plane, front first.
+pub fn plane(ids: &[u16]) -> Grid<bool> {
+    let mut plane = Grid::new(COLUMNS, ROWS, false);
+    for &id in ids {
+        let id = usize::from(id);
+        plane[(id % COLUMNS, id / COLUMNS)] = true;
+    }
+    plane
+}
+
+// The empty seat whose IDs either side are both taken.
+pub fn missing_seat(ids: &[u16]) -> Option<u16> {
+    let mut sorted = ids.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .windows(2)
+        .find(|w| w[1] == w[0] + 2)
+        .map(|w| w[0] + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day05>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day05>(input, expected)
+    }
+
+    fn seat(pass: &str, row: u16, column: u16, id: u16) {
+        let got = seat_id(pass).unwrap();
+        assert_eq!(got, id);
+        assert_eq!((got / COLUMNS as u16, got % COLUMNS as u16), (row, column));
+    }
+
+    fn invalid_pass(pass: &str, column: Option<usize>) {
+        assert_eq!(seat_id(pass).unwrap_err().column, column);
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/05.in"), "820");
+        }
+        part2 {
+            part2_example(include_str!("../cases/05.in"), "358");
+            duplicates("FBFBBFFRLR\nFBFBBFFRLR\nFBFBBFFRRR\n", "358");
+        }
+        seat {
+            example1("FBFBBFFRLR", 44, 5, 357);
+            example2("BFFFBBFRRR", 70, 7, 567);
+            example3("FFFBBBFRRR", 14, 7, 119);
+            example4("BBFFBBFRLL", 102, 4, 820);
+            first("FFFFFFFLLL", 0, 0, 0);
+            last("BBBBBBBRRR", 127, 7, 1023);
+        }
+        invalid_pass {
+            short("FBFBBFFRL", None);
+            row_letter("FBFBBRFRLR", Some(6));
+            column_letter("FBFBBFFRFR", Some(9));
+        }
+    }
+
+    #[test]
+    fn plane_map() {
+        let ids = Day05::parse(include_str!("../cases/05.in")).unwrap();
+        let map = plane(&ids).render(|&taken| if taken { '#' } else { '.' });
+        let rows: Vec<_> = map.lines().collect();
+        assert_eq!(rows.len(), ROWS);
+        assert_eq!(rows[44], ".....#.#");
+        assert_eq!(rows[102], "....#...");
+        assert_eq!(rows[0], "........");
+    }
+}
@@ -0,0 +1,73 @@
+use crate::bitset::Letters;
+use crate::sections::paragraphs;
+use crate::solution::Solution;
+use std::ops::{BitAnd, BitOr};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const NAME: &'static str = "Custom Customs";
+    // Each group's answers, one set per person.
+    type Input = Vec<Vec<Letters>>;
+
+    fn parse(input: &str) -> crate::Result<Vec<Vec<Letters>>> {
+        paragraphs(input)
+            .iter()
+            .map(|group| group.parse_lines(str::parse))
+            .collect()
+    }
+
+    fn part1(groups: &Vec<Vec<Letters>>) -> crate::Result<String> {
+        Ok(total(groups, Letters::NONE, Letters::bitor).to_string())
+    }
+
+    fn part2(groups: &Vec<Vec<Letters>>) -> crate::Result<String> {
+        Ok(total(groups, Letters::ALL, Letters::bitand).to_string())
+    }
+}
+
+// Combines each group's answers with f, starting from init, and adds up the sizes.
+pub fn total<F>(groups: &[Vec<Letters>], init: Letters, f: F) -> usize
+where
+    F: Fn(Letters, Letters) -> Letters,
+{
+    groups
+        .iter()
+        .map(|group| group.iter().copied().fold(init, &f).len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day06>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day06>(input, expected)
+    }
+
+    fn invalid_input(input: &str, line: usize) {
+        assert_eq!(Day06::parse(input).unwrap_err().line, Some(line));
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/06.in"), "11");
+            part1_all_letters("abcdefghijklmnopqrstuvwxyz\n", "26");
+        }
+        part2 {
+            part2_example(include_str!("../cases/06.in"), "6");
+            part2_nothing_shared("ab\ncd\n\nx\ny\n", "0");
+        }
+        invalid_input {
+            uppercase("ab\n\naB\n", 3);
+            space("a\na b\n", 2);
+        }
+    }
+}
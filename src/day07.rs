@@ -0,0 +1,126 @@
+use crate::graph::Graph;
+use crate::solution::Solution;
+use crate::Error;
+
+pub struct Day07;
+
+pub const MINE: &str = "shiny gold";
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const NAME: &'static str = "Handy Haversacks";
+    // An edge from each bag to each it contains, weighted by how many.
+    type Input = Graph<u32>;
+
+    fn parse(input: &str) -> crate::Result<Graph<u32>> {
+        let rules = crate::parse_lines(input, rule)?;
+        let mut graph = Graph::new();
+        let mut has_rule = Vec::new();
+        for (i, (outer, contents)) in rules.iter().enumerate() {
+            let outer_id = graph.node(outer);
+            has_rule.resize(graph.len(), false);
+            if std::mem::replace(&mut has_rule[outer_id], true) {
+                return Err(Error::parse(outer, "second rule for this bag").at_line(i + 1));
+            }
+            for (n, inner) in contents {
+                let inner_id = graph.node(inner);
+                graph.add_edge(outer_id, inner_id, *n);
+            }
+        }
+        Ok(graph)
+    }
+
+    fn part1(graph: &Graph<u32>) -> crate::Result<String> {
+        Ok(graph.ancestors(mine(graph)?).len().to_string())
+    }
+
+    fn part2(graph: &Graph<u32>) -> crate::Result<String> {
+        Ok(graph.weighted_sum(mine(graph)?)?.to_string())
+    }
+}
+
+fn mine(graph: &Graph<u32>) -> crate::Result<usize> {
+    graph
+        .id(MINE)
+        .ok_or_else(|| Error::invalid(format!("no rules mention {} bags", MINE)))
+}
+
+// light red bags contain 1 bright white bag, 2 muted yellow bags.
+// faded blue bags contain no other bags.
+fn rule(s: &str) -> crate::Result<(String, Vec<(u32, String)>)> {
+    let (outer, contents) = s
+        .split_once(" bags contain ")
+        .ok_or_else(|| Error::parse(s, "expected <colour> bags contain"))?;
+    let contents = contents
+        .strip_suffix('.')
+        .ok_or_else(|| Error::parse(s, "expected a full stop at the end"))?;
+    if contents == "no other bags" {
+        return Ok((outer.to_string(), Vec::new()));
+    }
+    let inner = contents
+        .split(", ")
+        .map(|item| {
+            let err = || Error::parse(item, "expected <number> <colour> bag(s)");
+            let (n, rest) = item.split_once(' ').ok_or_else(err)?;
+            let colour = rest
+                .strip_suffix(" bags")
+                .or_else(|| rest.strip_suffix(" bag"))
+                .ok_or_else(err)?;
+            Ok((crate::parse(n)?, colour.to_string()))
+        })
+        .collect::<crate::Result<_>>()?;
+    Ok((outer.to_string(), inner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day07>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day07>(input, expected)
+    }
+
+    fn invalid_input(input: &str, line: usize) {
+        assert_eq!(Day07::parse(input).unwrap_err().line, Some(line));
+    }
+
+    fn invalid_part2(input: &str) {
+        let graph = Day07::parse(input).unwrap();
+        assert!(Day07::part2(&graph).is_err());
+    }
+
+    const DEEP: &str = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.
+";
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/07.in"), "4");
+        }
+        part2 {
+            part2_example(include_str!("../cases/07.in"), "32");
+            part2_deep(DEEP, "126");
+        }
+        invalid_input {
+            no_contain("shiny gold bags hold 1 red bag.", 1);
+            no_full_stop("faded blue bags contain no other bags.\nshiny gold bags contain 1 red bag", 2);
+            bad_number("shiny gold bags contain one red bag.", 1);
+            repeated("a b bags contain no other bags.\na b bags contain 1 c d bag.", 2);
+        }
+        invalid_part2 {
+            no_gold("faded blue bags contain no other bags.");
+            cycle("shiny gold bags contain 1 dark red bag.\ndark red bags contain 2 shiny gold bags.");
+        }
+    }
+}
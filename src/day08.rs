@@ -0,0 +1,71 @@
+use crate::handheld::{self, Instruction, Outcome};
+use crate::solution::Solution;
+use crate::Error;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const NAME: &'static str = "Handheld Halting";
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> crate::Result<Vec<Instruction>> {
+        crate::parse_lines(input, str::parse)
+    }
+
+    fn part1(program: &Vec<Instruction>) -> crate::Result<String> {
+        match handheld::run(program)? {
+            Outcome::Looped(acc) => Ok(acc.to_string()),
+            Outcome::Terminated(_) => Err(Error::invalid("program terminates without looping")),
+        }
+    }
+
+    fn part2(program: &Vec<Instruction>) -> crate::Result<String> {
+        match handheld::run(&repaired(program)?)? {
+            Outcome::Terminated(acc) => Ok(acc.to_string()),
+            Outcome::Looped(_) => unreachable!("repair chose a swap that still loops"),
+        }
+    }
+}
+
+// The program with its one corrupted instruction swapped.
+pub fn repaired(program: &[Instruction]) -> crate::Result<Vec<Instruction>> {
+    let pc = handheld::repair(program)?;
+    let mut program = program.to_vec();
+    program[pc] = program[pc].flipped();
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day08>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day08>(input, expected)
+    }
+
+    fn invalid_part2(input: &str) {
+        let program = Day08::parse(input).unwrap();
+        assert!(Day08::part2(&program).is_err());
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/08.in"), "5");
+        }
+        part2 {
+            part2_example(include_str!("../cases/08.in"), "8");
+            // The first nop can't be the one: jmp +6 would leave the program.
+            part2_skips_outside("nop +6\nacc +2\nnop +2\njmp -3\nacc +1\n", "3");
+        }
+        invalid_part2 {
+            unrepairable("acc +1\njmp +0\nacc +1\njmp -2\n");
+        }
+    }
+}
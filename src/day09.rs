@@ -0,0 +1,106 @@
+use crate::day01::pairs_summing_to;
+use crate::solution::Solution;
+use crate::Error;
+
+pub struct Day09;
+
+// How many numbers come before the first that must be a sum of two of them.
+// The example uses 5.
+pub const PREAMBLE: usize = 25;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const NAME: &'static str = "Encoding Error";
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> crate::Result<Vec<u64>> {
+        crate::parse_lines(input, crate::parse)
+    }
+
+    fn part1(nums: &Vec<u64>) -> crate::Result<String> {
+        Ok(first_invalid(nums, PREAMBLE)?.to_string())
+    }
+
+    fn part2(nums: &Vec<u64>) -> crate::Result<String> {
+        Ok(weakness(nums, first_invalid(nums, PREAMBLE)?)?.to_string())
+    }
+}
+
+// The first number after the preamble that isn't the sum of two of the preamble numbers before it.
+pub fn first_invalid(nums: &[u64], preamble: usize) -> crate::Result<u64> {
+    if preamble == 0 || nums.len() <= preamble {
+        return Err(Error::invalid(format!(
+            "need more than {} numbers for a preamble of {}",
+            nums.len(),
+            preamble
+        )));
+    }
+    nums.windows(preamble + 1)
+        .map(|w| w[preamble])
+        .zip(nums.windows(preamble))
+        .find(|&(n, before)| pairs_summing_to(before, n).next().is_none())
+        .map(|(n, _)| n)
+        .ok_or_else(|| Error::invalid("every number is a sum of two before it"))
+}
+
+// The smallest plus the largest of a contiguous range of at least two numbers that sum to target.
+pub fn weakness(nums: &[u64], target: u64) -> crate::Result<u64> {
+    // The numbers are never negative, so growing the range only adds and shrinking it only removes.
+    let mut start = 0;
+    let mut sum = 0;
+    for (end, &n) in nums.iter().enumerate() {
+        sum += n;
+        while sum > target && start < end {
+            sum -= nums[start];
+            start += 1;
+        }
+        if sum == target && end > start {
+            let range = &nums[start..=end];
+            return Ok(range.iter().min().unwrap() + range.iter().max().unwrap());
+        }
+    }
+    Err(Error::invalid(format!(
+        "no range of two or more numbers sums to {}",
+        target
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests;
+
+    const EXAMPLE: &str = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576\n";
+
+    fn invalid(input: &str, preamble: usize, expected: u64) {
+        let nums = Day09::parse(input).unwrap();
+        assert_eq!(first_invalid(&nums, preamble).unwrap(), expected);
+    }
+
+    fn weak(input: &str, target: u64, expected: u64) {
+        let nums = Day09::parse(input).unwrap();
+        assert_eq!(weakness(&nums, target).unwrap(), expected);
+    }
+
+    fn no_answer(input: &str, preamble: usize) {
+        let nums = Day09::parse(input).unwrap();
+        assert!(first_invalid(&nums, preamble).is_err());
+    }
+
+    tests! {
+        invalid {
+            example(EXAMPLE, 5, 127);
+            equal_values("5\n5\n10\n3\n", 2, 3);
+            powers_of_two("1\n2\n4\n8\n16\n", 3, 8);
+            preamble_of_25(&(1..=25).chain([26, 49, 100]).map(|n| n.to_string() + "\n").collect::<String>(), 25, 100);
+        }
+        weak {
+            example_weakness(EXAMPLE, 127, 62);
+            not_just_the_number("1\n5\n2\n3\n", 5, 5);
+        }
+        no_answer {
+            too_short("1\n2\n", 2);
+            all_valid("1\n2\n3\n5\n", 2);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::solution::Solution;
+use crate::Error;
+
+pub struct Day10;
+
+// The most an adapter's rating can exceed whatever it plugs into.
+const MAX_STEP: u32 = 3;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const NAME: &'static str = "Adapter Array";
+    // Every joltage in the chain, in order: the outlet, each adapter, then the device.
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> crate::Result<Vec<u32>> {
+        let mut joltages = crate::parse_lines(input, crate::parse)?;
+        joltages.push(0);
+        joltages.sort_unstable();
+        let device = joltages[joltages.len() - 1] + MAX_STEP;
+        joltages.push(device);
+        if let Some(w) = joltages
+            .windows(2)
+            .find(|w| !(1..=MAX_STEP).contains(&(w[1] - w[0])))
+        {
+            return Err(Error::invalid(format!(
+                "can't use every adapter: {} and {} differ by {}",
+                w[0],
+                w[1],
+                w[1] - w[0]
+            )));
+        }
+        Ok(joltages)
+    }
+
+    fn part1(chain: &Vec<u32>) -> crate::Result<String> {
+        let diffs = differences(chain);
+        Ok((diffs[1] * diffs[3]).to_string())
+    }
+
+    fn part2(chain: &Vec<u32>) -> crate::Result<String> {
+        Ok(arrangements(chain)?.to_string())
+    }
+}
+
+// How many times each difference, 0 to 3, occurs between neighbours in the chain.
+pub fn differences(chain: &[u32]) -> [usize; MAX_STEP as usize + 1] {
+    let mut diffs = [0; MAX_STEP as usize + 1];
+    for w in chain.windows(2) {
+        diffs[(w[1] - w[0]) as usize] += 1;
+    }
+    diffs
+}
+
+// How many ways there are to get from the outlet to the device,
+// using some of the adapters in between.
+// Long runs of adapters one jolt apart make this grow quickly, hence u128,
+// but enough of them still overflow that.
+pub fn arrangements(chain: &[u32]) -> crate::Result<u128> {
+    // ways[i]: how many ways there are to reach chain[i].
+    let mut ways: Vec<u128> = vec![0; chain.len()];
+    ways[0] = 1;
+    for i in 1..chain.len() {
+        let reachable_from = (0..i)
+            .rev()
+            .take_while(|&j| chain[i] - chain[j] <= MAX_STEP);
+        ways[i] = reachable_from
+            .map(|j| ways[j])
+            .try_fold(0_u128, u128::checked_add)
+            .ok_or_else(|| {
+                Error::invalid(format!(
+                    "more than {} arrangements reach {} jolts",
+                    u128::MAX,
+                    chain[i]
+                ))
+            })?;
+    }
+    Ok(ways[chain.len() - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day10>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day10>(input, expected)
+    }
+
+    fn invalid_input(input: &str) {
+        assert!(Day10::parse(input).is_err());
+    }
+
+    // Adapters 1 to n, all one jolt apart.
+    fn run_of(n: u32) -> String {
+        (1..=n).map(|j| format!("{}\n", j)).collect()
+    }
+
+    fn overflow(n: u32) {
+        let chain = Day10::parse(&run_of(n)).unwrap();
+        assert!(Day10::part2(&chain).is_err());
+    }
+
+    const LARGER: &str = "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n";
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/10.in"), "35");
+            part1_larger(LARGER, "220");
+        }
+        part2 {
+            part2_example(include_str!("../cases/10.in"), "8");
+            part2_larger(LARGER, "19208");
+            // Tribonacci numbers: the longest run whose count fits in a u128.
+            part2_long_run(&run_of(146), "269216838928454720696964324302545675954");
+        }
+        invalid_input {
+            gap("1\n5\n");
+            duplicate("1\n2\n2\n");
+            first_too_high("4\n");
+        }
+        overflow {
+            overflow_long_run(147);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use crate::conway::{NeighCount, Rule};
+use crate::grid::{Grid, ALL_DIRECTIONS};
+use crate::solution::Solution;
+use crate::Error;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const NAME: &'static str = "Seating System";
+    type Input = Seats;
+
+    fn parse(input: &str) -> crate::Result<Seats> {
+        let grid = Grid::parse(input, |c| match c {
+            '.' => Ok(Cell::Floor),
+            'L' => Ok(Cell::Empty),
+            '#' => Ok(Cell::Occupied),
+            _ => Err(Error::parse(&c.to_string(), "expected ., L or #")),
+        })?;
+        Ok(Seats::new(grid))
+    }
+
+    fn part1(seats: &Seats) -> crate::Result<String> {
+        Ok(occupied_when_stable(seats, Sight::Adjacent).to_string())
+    }
+
+    fn part2(seats: &Seats) -> crate::Result<String> {
+        Ok(occupied_when_stable(seats, Sight::LineOfSight).to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+// Which seats count as a seat's neighbours, and how many of them occupied make people leave.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sight {
+    // The up to eight seats next to it.
+    Adjacent,
+    // The first seat in each of the eight directions, looking past floor.
+    LineOfSight,
+}
+
+impl Sight {
+    pub fn tolerance(self) -> NeighCount {
+        match self {
+            Sight::Adjacent => 4,
+            Sight::LineOfSight => 5,
+        }
+    }
+
+    // Empty seats with no occupied neighbours fill;
+    // occupied seats stay occupied while fewer than tolerance neighbours are.
+    pub fn rule(self) -> Rule {
+        let stays: Vec<_> = (0..self.tolerance()).collect();
+        Rule::new(&[0], &stays)
+    }
+}
+
+// The seating area, with each seat numbered in reading order.
+pub struct Seats {
+    pub grid: Grid<Cell>,
+    // Where each seat is.
+    pub positions: Vec<(usize, usize)>,
+}
+
+impl Seats {
+    pub fn new(grid: Grid<Cell>) -> Self {
+        let positions = grid.positions(|&c| c != Cell::Floor).collect();
+        Self { grid, positions }
+    }
+
+    // Which seats are occupied at the start.
+    pub fn initial(&self) -> Vec<bool> {
+        self.positions
+            .iter()
+            .map(|&pos| self.grid[pos] == Cell::Occupied)
+            .collect()
+    }
+
+    // For each seat, the seats that are its neighbours.
+    // Worked out once, so each generation only has to look up occupancy.
+    pub fn neighbours(&self, sight: Sight) -> Vec<Vec<usize>> {
+        let mut index = self.grid.map(|_| None);
+        for (i, &pos) in self.positions.iter().enumerate() {
+            index[pos] = Some(i);
+        }
+        let reach = match sight {
+            Sight::Adjacent => 1,
+            Sight::LineOfSight => self.grid.width().max(self.grid.height()),
+        };
+        self.positions
+            .iter()
+            .map(|&(x, y)| {
+                ALL_DIRECTIONS
+                    .iter()
+                    .filter_map(|&(dx, dy)| {
+                        (1..=reach as isize)
+                            .map(|n| index.get(x as isize + dx * n, y as isize + dy * n))
+                            .take_while(Option::is_some)
+                            .find_map(|seat| *seat.unwrap())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn render(&self, occupied: &[bool]) -> String {
+        let mut grid = self.grid.map(|_| '.');
+        for (&pos, &occ) in self.positions.iter().zip(occupied) {
+            grid[pos] = if occ { '#' } else { 'L' };
+        }
+        grid.render(|&c| c)
+    }
+}
+
+// The next generation, or None if nothing changes.
+pub fn step(neighbours: &[Vec<usize>], rule: Rule, occupied: &[bool]) -> Option<Vec<bool>> {
+    let next: Vec<_> = neighbours
+        .iter()
+        .zip(occupied)
+        .map(|(neigh, &occ)| {
+            let count = neigh.iter().filter(|&&n| occupied[n]).count();
+            rule.next(occ, count as NeighCount)
+        })
+        .collect();
+    (next != occupied).then_some(next)
+}
+
+// Steps until nothing changes, calling generation with each generation's number
+// and occupancy, starting with the initial one at 0.
+pub fn run_until_stable<F>(seats: &Seats, sight: Sight, mut generation: F) -> Vec<bool>
+where
+    F: FnMut(usize, &[bool]),
+{
+    let neighbours = seats.neighbours(sight);
+    let rule = sight.rule();
+    let mut occupied = seats.initial();
+    generation(0, &occupied);
+    let mut t = 0;
+    while let Some(next) = step(&neighbours, rule, &occupied) {
+        occupied = next;
+        t += 1;
+        generation(t, &occupied);
+    }
+    occupied
+}
+
+pub fn occupied_when_stable(seats: &Seats, sight: Sight) -> usize {
+    let occupied = run_until_stable(seats, sight, |_, _| {});
+    occupied.iter().filter(|&&occ| occ).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day11>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day11>(input, expected)
+    }
+
+    // How many of the seat at (x, y)'s neighbours are occupied.
+    fn sees(input: &str, x: usize, y: usize, expected: usize) {
+        let seats = Day11::parse(input).unwrap();
+        let i = seats.positions.iter().position(|&p| p == (x, y)).unwrap();
+        let occupied = seats.initial();
+        let neighbours = &seats.neighbours(Sight::LineOfSight)[i];
+        assert_eq!(
+            neighbours.iter().filter(|&&n| occupied[n]).count(),
+            expected
+        );
+    }
+
+    const EIGHT: &str = ".......#.
+...#.....
+.#.......
+.........
+..#L....#
+....#....
+.........
+#........
+...#.....
+";
+
+    const EMPTY_SEAT: &str = ".............
+.L.L.#.#.#.#.
+.............
+";
+
+    const NONE_VISIBLE: &str = ".##.##.
+#.#.#.#
+##...##
+...L...
+##...##
+#.#.#.#
+.##.##.
+";
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/11.in"), "37");
+        }
+        part2 {
+            part2_example(include_str!("../cases/11.in"), "26");
+        }
+        sees {
+            eight(EIGHT, 3, 4, 8);
+            blocked_by_empty_seat(EMPTY_SEAT, 1, 1, 0);
+            none_visible(NONE_VISIBLE, 3, 3, 0);
+        }
+    }
+
+    #[test]
+    fn generations() {
+        let seats = Day11::parse(include_str!("../cases/11.in")).unwrap();
+        let mut rendered = Vec::new();
+        run_until_stable(&seats, Sight::Adjacent, |_, occupied| {
+            rendered.push(seats.render(occupied))
+        });
+        assert_eq!(rendered[0], include_str!("../cases/11.in"));
+        assert_eq!(rendered[1].lines().next(), Some("#.##.##.##"));
+        assert!(rendered[2].starts_with("#.LL.L#.##\n#LLLLLL.L#\n"));
+        // The initial layout, then five generations, the last of which is stable.
+        assert_eq!(rendered.len(), 6);
+    }
+}
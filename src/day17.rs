@@ -0,0 +1,47 @@
+use crate::conway::{active, Simulation};
+use crate::solution::Solution;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const NAME: &'static str = "Conway Cubes";
+    type Input = Vec<(usize, usize)>;
+
+    fn parse(input: &str) -> crate::Result<Vec<(usize, usize)>> {
+        active(input)
+    }
+
+    fn part1(initial: &Vec<(usize, usize)>) -> crate::Result<String> {
+        Ok(Simulation::new(3, 6, initial).run().to_string())
+    }
+
+    fn part2(initial: &Vec<(usize, usize)>) -> crate::Result<String> {
+        Ok(Simulation::new(4, 6, initial).run().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day17>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day17>(input, expected)
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/17.in"), "112");
+            part1_trailing_blank_line(".#.\n..#\n###\n\n", "112");
+        }
+        part2 {
+            part2_example(include_str!("../cases/17.in"), "848");
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::solution::Solution;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const NAME: &'static str = "Crab Cups";
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> crate::Result<Vec<u8>> {
+        let cups: Vec<_> = input
+            .chars()
+            .filter_map(|c| c.to_digit(10).map(|c| c as u8))
+            .collect();
+        // Each move picks up three cups and needs a destination besides the current one.
+        if cups.len() < 5 {
+            return Err(crate::Error::invalid("need at least 5 cups"));
+        }
+        // game links each label to the next, so the labels must be 1 to n, each once.
+        let mut seen = vec![false; cups.len() + 1];
+        for &cup in &cups {
+            let label = usize::from(cup);
+            if label == 0 || label > cups.len() {
+                return Err(crate::Error::invalid(format!(
+                    "cup {} is not between 1 and {}",
+                    cup,
+                    cups.len()
+                )));
+            }
+            if seen[label] {
+                return Err(crate::Error::invalid(format!("cup {} appears twice", cup)));
+            }
+            seen[label] = true;
+        }
+        Ok(cups)
+    }
+
+    fn part1(cups: &Vec<u8>) -> crate::Result<String> {
+        Ok(game(cups, cups.len() as u32, 100, cups.len() as u32 - 1)
+            .iter()
+            .fold(0, |a, x| a * 10 + x)
+            .to_string())
+    }
+
+    fn part2(cups: &Vec<u8>) -> crate::Result<String> {
+        Ok(game(cups, 1_000_000, 10_000_000, 2)
+            .iter()
+            .product::<u64>()
+            .to_string())
+    }
+}
+
+// All the `as` casts are regrettable,
+// but using Vec<u32> does make the code faster than Vec<usize>
+pub fn game(cups: &[u8], ncups: u32, rounds: usize, after1: u32) -> Vec<u64> {
+    let mut right: Vec<_> = (1..=(ncups + 1)).collect();
+    for (&l, &r) in cups.iter().zip(cups.iter().skip(1)) {
+        right[usize::from(l)] = u32::from(r);
+    }
+    if ncups as usize > cups.len() {
+        right[cups[cups.len() - 1] as usize] = cups.len() as u32 + 1;
+        right[ncups as usize] = u32::from(cups[0]);
+    } else {
+        right[cups[cups.len() - 1] as usize] = u32::from(cups[0]);
+    }
+
+    let mut current = u32::from(cups[0]);
+
+    for _ in 0..rounds {
+        let pickup1 = right[current as usize];
+        let pickup2 = right[pickup1 as usize];
+        let pickup3 = right[pickup2 as usize];
+        let after_pickup = right[pickup3 as usize];
+
+        let mut dest = if current == 1 { ncups } else { current - 1 };
+        while dest == pickup1 || dest == pickup2 || dest == pickup3 {
+            dest = if dest == 1 { ncups } else { dest - 1 };
+        }
+
+        let right_of_dest = right[dest as usize];
+
+        right[current as usize] = after_pickup;
+        right[dest as usize] = pickup1;
+        right[pickup3 as usize] = right_of_dest;
+
+        current = after_pickup;
+    }
+
+    let mut current = 1;
+    (0..after1)
+        .map(|_| {
+            let x = u64::from(right[current as usize]);
+            current = x;
+            x
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{check_part1, check_part2};
+    use crate::tests;
+
+    fn part1(input: &str, expected: &str) {
+        check_part1::<Day23>(input, expected)
+    }
+
+    fn part2(input: &str, expected: &str) {
+        check_part2::<Day23>(input, expected)
+    }
+
+    fn invalid_input(input: &str) {
+        assert!(Day23::parse(input).is_err());
+    }
+
+    fn after_moves(input: &str, moves: usize, expected: &[u64]) {
+        let cups = Day23::parse(input).unwrap();
+        assert_eq!(
+            game(&cups, cups.len() as u32, moves, cups.len() as u32 - 1),
+            expected
+        );
+    }
+
+    tests! {
+        part1 {
+            part1_example(include_str!("../cases/23.in"), "67384529");
+        }
+        part2 {
+            part2_example(include_str!("../cases/23.in"), "149245887792");
+        }
+        invalid_input {
+            empty("");
+            zero("0");
+            too_few("4312");
+            label_too_high("123459");
+            repeated("3891254");
+            gap("389125468");
+        }
+        after_moves {
+            ten_moves("389125467", 10, &[9, 2, 6, 5, 8, 3, 7, 4]);
+        }
+    }
+}
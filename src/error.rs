@@ -0,0 +1,149 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Io(std::io::Error),
+    // The message from the failed parse.
+    Parse(String),
+    // Input that parsed, but that a solution can't handle.
+    Invalid(String),
+}
+
+// An input error, with as much of its location as is known.
+// Lines and columns count from 1.
+#[derive(Debug)]
+pub struct Error {
+    pub path: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub token: Option<String>,
+    pub kind: ErrorKind,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn io(path: &str, err: std::io::Error) -> Self {
+        Self {
+            path: Some(path.to_string()),
+            line: None,
+            column: None,
+            token: None,
+            kind: ErrorKind::Io(err),
+        }
+    }
+
+    pub fn parse<E: fmt::Display>(token: &str, err: E) -> Self {
+        Self {
+            path: None,
+            line: None,
+            column: None,
+            token: Some(token.to_string()),
+            kind: ErrorKind::Parse(err.to_string()),
+        }
+    }
+
+    pub fn invalid<S: Into<String>>(msg: S) -> Self {
+        Self {
+            path: None,
+            line: None,
+            column: None,
+            token: None,
+            kind: ErrorKind::Invalid(msg.into()),
+        }
+    }
+
+    // Location setters only fill in what isn't already known,
+    // so an inner, more specific location wins.
+    pub fn in_file(mut self, path: &str) -> Self {
+        self.path.get_or_insert_with(|| path.to_string());
+        self
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column.get_or_insert(column);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // file:line:column: like a compiler would.
+        let location: Vec<_> = [
+            self.path.clone(),
+            self.line.map(|l| l.to_string()),
+            self.column.map(|c| c.to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(":"))?;
+        }
+        match (&self.kind, &self.token) {
+            (ErrorKind::Io(err), _) => write!(f, "couldn't read file: {}", err),
+            (ErrorKind::Parse(msg), Some(token)) => write!(f, "can't parse {:?}: {}", token, msg),
+            (ErrorKind::Parse(msg), None) | (ErrorKind::Invalid(msg), _) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(err) => Some(err),
+            ErrorKind::Parse(_) | ErrorKind::Invalid(_) => None,
+        }
+    }
+}
+
+pub trait OrExit<T> {
+    // Prints the error and exits with a non-zero status, instead of panicking.
+    fn or_exit(self) -> T;
+}
+
+impl<T> OrExit<T> for Result<T> {
+    fn or_exit(self) -> T {
+        self.unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            std::process::exit(1)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_full_location() {
+        let err = crate::numbers::<u8>("1 2 300")
+            .unwrap_err()
+            .at_line(4)
+            .in_file("input.txt");
+        assert_eq!(
+            err.to_string(),
+            "input.txt:4:5: can't parse \"300\": number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn display_no_location() {
+        let err = crate::parse::<u32>("x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "can't parse \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn inner_location_wins() {
+        let err = Error::parse("x", "bad").at_line(2).at_line(5);
+        assert_eq!(err.line, Some(2));
+    }
+}
@@ -0,0 +1,183 @@
+// Directed graphs with named nodes and weighted edges.
+
+use crate::{Error, Result};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default)]
+pub struct Graph<W> {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    edges: Vec<Vec<(usize, W)>>,
+    // For each node, the nodes with an edge to it.
+    reverse: Vec<Vec<usize>>,
+}
+
+impl<W> Graph<W> {
+    pub fn new() -> Self {
+        Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+            reverse: Vec::new(),
+        }
+    }
+
+    // The node with this name, added if it isn't already there.
+    pub fn node(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(Vec::new());
+        self.reverse.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        self.edges[from].push((to, weight));
+        self.reverse[to].push(from);
+    }
+
+    pub fn edges(&self, id: usize) -> &[(usize, W)] {
+        &self.edges[id]
+    }
+
+    // Nodes with a path to id, not counting id itself unless it's on a cycle.
+    pub fn ancestors(&self, id: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![id];
+        let mut found = Vec::new();
+        while let Some(n) = stack.pop() {
+            for &from in &self.reverse[n] {
+                if !seen[from] {
+                    seen[from] = true;
+                    found.push(from);
+                    stack.push(from);
+                }
+            }
+        }
+        found
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    Unvisited,
+    InProgress,
+    Done(u64),
+}
+
+impl<W: Copy + Into<u64>> Graph<W> {
+    // Over every path from id, the product of its weights, all added up.
+    // So if each edge says how many of one thing another contains,
+    // this is how many things id contains in total.
+    // Errors if any node reachable from id is on a cycle, or if the total overflows.
+    pub fn weighted_sum(&self, id: usize) -> Result<u64> {
+        let mut visits = vec![Visit::Unvisited; self.len()];
+        self.weighted_sum_from(id, &mut visits)
+    }
+
+    fn weighted_sum_from(&self, id: usize, visits: &mut [Visit]) -> Result<u64> {
+        match visits[id] {
+            Visit::Done(sum) => return Ok(sum),
+            Visit::InProgress => {
+                return Err(Error::invalid(format!("cycle through {}", self.name(id))))
+            }
+            Visit::Unvisited => visits[id] = Visit::InProgress,
+        }
+        let mut sum: u64 = 0;
+        for &(to, weight) in &self.edges[id] {
+            let inner = self.weighted_sum_from(to, visits)?;
+            sum = inner
+                .checked_add(1)
+                .and_then(|n| n.checked_mul(weight.into()))
+                .and_then(|n| n.checked_add(sum))
+                .ok_or_else(|| {
+                    Error::invalid(format!("weighted sum from {} overflows", self.name(id)))
+                })?;
+        }
+        visits[id] = Visit::Done(sum);
+        Ok(sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -2-> b -3-> c, a -1-> c
+    fn small() -> Graph<u32> {
+        let mut g = Graph::new();
+        let (a, b, c) = (g.node("a"), g.node("b"), g.node("c"));
+        g.add_edge(a, b, 2);
+        g.add_edge(b, c, 3);
+        g.add_edge(a, c, 1);
+        g
+    }
+
+    #[test]
+    fn nodes_are_named_once() {
+        let mut g = small();
+        assert_eq!(g.node("b"), 1);
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.id("c"), Some(2));
+        assert_eq!(g.id("d"), None);
+        assert_eq!(g.name(0), "a");
+    }
+
+    #[test]
+    fn ancestors() {
+        let g = small();
+        let mut found = g.ancestors(2);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1]);
+        assert!(g.ancestors(0).is_empty());
+    }
+
+    #[test]
+    fn weighted_sum() {
+        let g = small();
+        // 2 b, each with 3 c, and 1 more c.
+        assert_eq!(g.weighted_sum(0).unwrap(), 2 + 2 * 3 + 1);
+        assert_eq!(g.weighted_sum(2).unwrap(), 0);
+    }
+
+    #[test]
+    fn cycle() {
+        let mut g = small();
+        g.add_edge(2, 1, 1);
+        let err = g.weighted_sum(0).unwrap_err();
+        assert!(err.to_string().contains("cycle"), "{}", err);
+        let mut found = g.ancestors(1);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut g = Graph::new();
+        let ids: Vec<_> = (0..8).map(|i| g.node(&i.to_string())).collect();
+        for w in ids.windows(2) {
+            g.add_edge(w[0], w[1], u32::MAX);
+        }
+        assert!(g.weighted_sum(0).is_err());
+    }
+}
@@ -0,0 +1,364 @@
+use crate::{Error, Result};
+
+pub type Dir = (isize, isize);
+
+pub const ORTHOGONAL: [Dir; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub const ALL_DIRECTIONS: [Dir; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+// A rectangular grid, stored row by row.
+// Positions are (x, y), with x increasing to the right and y increasing downward.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Parses one row per line, mapping each character to a cell.
+    // Errors from the mapper get the line and column of their character.
+    // Trailing empty lines are ignored, but other empty lines are errors,
+    // so a grid is either empty or at least 1x1.
+    pub fn parse<F>(s: &str, mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (y, line) in s.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).map_err(|e| e.at_line(y + 1).at_column(x + 1))?);
+            }
+            let row_width = cells.len() - before;
+            if row_width == 0 {
+                return Err(Error::invalid("row has no cells").at_line(y + 1));
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    return Err(Error::invalid(format!(
+                        "row has {} cells, but earlier rows have {}",
+                        row_width, w
+                    ))
+                    .at_line(y + 1))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "{}x{} grid can't have {} cells",
+            width,
+            height,
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        0 <= x && (x as usize) < self.width && 0 <= y && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        if self.in_bounds(x, y) {
+            Some(&self.cells[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.in_bounds(x, y) {
+            Some(&mut self.cells[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    // Treats the grid as repeating infinitely in both directions.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let (x, y) = self.wrap(x, y);
+        &self[(x, y)]
+    }
+
+    fn wrap(&self, x: isize, y: isize) -> (usize, usize) {
+        (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    // In-bounds positions one step away from (x, y) in each of the directions.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        dirs: &'a [Dir],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        dirs.iter().filter_map(move |&(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if self.in_bounds(nx, ny) {
+                Some((nx as usize, ny as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    // Positions one step away from (x, y) in each of the directions,
+    // wrapping around at the edges.
+    pub fn neighbours_wrapping<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        dirs: &'a [Dir],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        dirs.iter()
+            .map(move |&(dx, dy)| self.wrap(x as isize + dx, y as isize + dy))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn positions<'a, P>(&'a self, mut pred: P) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.iter()
+            .filter_map(move |(pos, cell)| if pred(cell) { Some(pos) } else { None })
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // One line per row, each cell rendered by f.
+    pub fn render<F: FnMut(&T) -> char>(&self, mut f: F) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_cells(width, height, vec![fill; width * height])
+    }
+
+    fn rearranged<F>(&self, width: usize, height: usize, src: F) -> Self
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(self[src(x, y)].clone());
+            }
+        }
+        Self::from_cells(width, height, cells)
+    }
+
+    // Swaps x and y.
+    pub fn transpose(&self) -> Self {
+        self.rearranged(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let h = self.height;
+        self.rearranged(self.height, self.width, |x, y| (y, h - 1 - x))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let w = self.width;
+        self.rearranged(self.height, self.width, |x, y| (w - 1 - y, x))
+    }
+
+    // Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        self.rearranged(self.width, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    // Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        self.rearranged(self.width, self.height, |x, y| (x, h - 1 - y))
+    }
+}
+
+impl<T> std::ops::Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) out of bounds",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) out of bounds",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+// The mapper for the common case of # for true and . for false.
+pub fn hash_or_dot(c: char) -> Result<bool> {
+    match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(Error::parse(&c.to_string(), "expected # or .")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const L: &str = "#..\n#..\n##.\n";
+
+    fn l() -> Grid<bool> {
+        Grid::parse(L, hash_or_dot).unwrap()
+    }
+
+    fn render(g: &Grid<bool>) -> String {
+        g.render(|&b| if b { '#' } else { '.' })
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let g = l();
+        assert_eq!((g.width(), g.height()), (3, 3));
+        assert!(g[(0, 2)]);
+        assert!(!g[(2, 0)]);
+        assert_eq!(render(&g), L);
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let err = Grid::parse("#.\n.x\n", hash_or_dot).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn parse_ragged() {
+        let err = Grid::parse("#.\n.\n", hash_or_dot).unwrap_err();
+        assert_eq!(err.line, Some(2));
+    }
+
+    #[test]
+    fn parse_empty_row() {
+        let err = Grid::parse("#\n\n#\n", hash_or_dot).unwrap_err();
+        assert_eq!(err.line, Some(2));
+        let err = Grid::parse("\n#\n", hash_or_dot).unwrap_err();
+        assert_eq!(err.line, Some(1));
+        assert_eq!(Grid::parse("", hash_or_dot).unwrap().height(), 0);
+        assert_eq!(Grid::parse("\n", hash_or_dot).unwrap().height(), 0);
+        assert_eq!(
+            Grid::parse("#\n\n\n", hash_or_dot).unwrap(),
+            Grid::parse("#", hash_or_dot).unwrap()
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let g = l();
+        assert_eq!(g.get(0, 0), Some(&true));
+        assert_eq!(g.get(-1, 0), None);
+        assert_eq!(g.get(3, 0), None);
+        assert_eq!(g.get_wrapping(-3, 5), &true);
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = l();
+        let mut corner: Vec<_> = g.neighbours(0, 0, &ALL_DIRECTIONS).collect();
+        corner.sort_unstable();
+        assert_eq!(corner, vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(g.neighbours(1, 1, &ORTHOGONAL).count(), 4);
+        let mut wrapped: Vec<_> = g.neighbours_wrapping(0, 0, &ORTHOGONAL).collect();
+        wrapped.sort_unstable();
+        assert_eq!(wrapped, vec![(0, 1), (0, 2), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn positions() {
+        assert_eq!(
+            l().positions(|&b| b).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        let g = l();
+        assert_eq!(render(&g.transpose()), "###\n..#\n...\n");
+        assert_eq!(render(&g.rotate_clockwise()), "###\n#..\n...\n");
+        assert_eq!(render(&g.rotate_counterclockwise()), "...\n..#\n###\n");
+        assert_eq!(render(&g.flip_horizontal()), "..#\n..#\n.##\n");
+        assert_eq!(render(&g.flip_vertical()), "##.\n#..\n#..\n");
+        assert_eq!(g.rotate_clockwise().rotate_counterclockwise(), g);
+    }
+
+    #[test]
+    fn non_square_rotation() {
+        let g = Grid::parse("##.\n", hash_or_dot).unwrap();
+        assert_eq!(render(&g.rotate_clockwise()), "#\n#\n.\n");
+        assert_eq!(render(&g.rotate_counterclockwise()), ".\n#\n#\n");
+    }
+}
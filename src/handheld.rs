@@ -0,0 +1,208 @@
+// The handheld game console's boot code: an accumulator and three instructions.
+
+use crate::{Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Acc,
+    Jmp,
+    Nop,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: Op,
+    pub arg: i64,
+}
+
+impl std::str::FromStr for Instruction {
+    type Err = Error;
+
+    // acc +1
+    fn from_str(s: &str) -> Result<Self> {
+        let (op, arg) = s
+            .split_once(' ')
+            .ok_or_else(|| Error::parse(s, "expected an operation and an argument"))?;
+        let op = match op {
+            "acc" => Op::Acc,
+            "jmp" => Op::Jmp,
+            "nop" => Op::Nop,
+            _ => return Err(Error::parse(op, "expected acc, jmp or nop")),
+        };
+        let arg = crate::parse(arg).map_err(|e| e.at_column(op_len(s) + 2))?;
+        Ok(Instruction { op, arg })
+    }
+}
+
+fn op_len(s: &str) -> usize {
+    s.find(' ').unwrap_or(s.len())
+}
+
+impl Instruction {
+    // Where execution goes after this instruction, at pc.
+    pub fn next(&self, pc: usize) -> i64 {
+        match self.op {
+            Op::Jmp => pc as i64 + self.arg,
+            Op::Acc | Op::Nop => pc as i64 + 1,
+        }
+    }
+
+    // jmp and nop swapped; acc left as it is.
+    pub fn flipped(&self) -> Self {
+        let op = match self.op {
+            Op::Acc => Op::Acc,
+            Op::Jmp => Op::Nop,
+            Op::Nop => Op::Jmp,
+        };
+        Instruction { op, ..*self }
+    }
+}
+
+// The accumulator when the program stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    // Went to the instruction just after the last.
+    Terminated(i64),
+    // About to run an instruction a second time.
+    Looped(i64),
+}
+
+pub fn run(program: &[Instruction]) -> Result<Outcome> {
+    run_traced(program, |_, _, _| {})
+}
+
+// Calls trace with the pc, instruction and accumulator before each instruction runs.
+// Errors if execution goes anywhere outside the program but its end.
+pub fn run_traced<F>(program: &[Instruction], mut trace: F) -> Result<Outcome>
+where
+    F: FnMut(usize, &Instruction, i64),
+{
+    let mut visited = vec![false; program.len()];
+    let mut pc = 0;
+    let mut acc: i64 = 0;
+    while pc != program.len() {
+        if std::mem::replace(&mut visited[pc], true) {
+            return Ok(Outcome::Looped(acc));
+        }
+        let inst = &program[pc];
+        trace(pc, inst, acc);
+        if inst.op == Op::Acc {
+            acc += inst.arg;
+        }
+        pc = in_program(program, pc, inst.next(pc))?;
+    }
+    Ok(Outcome::Terminated(acc))
+}
+
+// next as an index, if it's in the program or its end.
+fn in_program(program: &[Instruction], pc: usize, next: i64) -> Result<usize> {
+    usize::try_from(next)
+        .ok()
+        .filter(|&n| n <= program.len())
+        .ok_or_else(|| {
+            Error::invalid(format!(
+                "instruction {} jumps to {}, outside the program",
+                pc, next
+            ))
+        })
+}
+
+// Which indices, up to and including the end, reach the end without any changes.
+fn reaches_end(program: &[Instruction]) -> Vec<bool> {
+    let mut comes_from = vec![Vec::new(); program.len() + 1];
+    for (pc, inst) in program.iter().enumerate() {
+        if let Ok(next) = in_program(program, pc, inst.next(pc)) {
+            comes_from[next].push(pc);
+        }
+    }
+    let mut reaches = vec![false; program.len() + 1];
+    reaches[program.len()] = true;
+    let mut stack = vec![program.len()];
+    while let Some(pc) = stack.pop() {
+        for &from in &comes_from[pc] {
+            if !std::mem::replace(&mut reaches[from], true) {
+                stack.push(from);
+            }
+        }
+    }
+    reaches
+}
+
+// The index of the one jmp or nop that, swapped, makes the program terminate.
+//
+// Rather than trying each swap, only the instructions the looping program actually runs
+// are candidates, and a swap works if it leads somewhere that already reaches the end.
+// That can't go through the swapped instruction again:
+// everything the looping program runs only leads back into the loop.
+pub fn repair(program: &[Instruction]) -> Result<usize> {
+    let reaches = reaches_end(program);
+    let mut candidates = Vec::new();
+    match run_traced(program, |pc, _, _| candidates.push(pc))? {
+        Outcome::Looped(_) => (),
+        Outcome::Terminated(_) => {
+            return Err(Error::invalid("program already terminates"));
+        }
+    }
+    candidates
+        .into_iter()
+        .find(|&pc| {
+            let inst = program[pc];
+            inst.op != Op::Acc
+                && in_program(program, pc, inst.flipped().next(pc)).is_ok_and(|n| reaches[n])
+        })
+        .ok_or_else(|| Error::invalid("no single jmp or nop swap makes the program terminate"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(s: &str) -> Vec<Instruction> {
+        crate::parse_lines(s, str::parse).unwrap()
+    }
+
+    const EXAMPLE: &str =
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+    #[test]
+    fn loops() {
+        assert_eq!(run(&program(EXAMPLE)).unwrap(), Outcome::Looped(5));
+    }
+
+    #[test]
+    fn repairs() {
+        let mut p = program(EXAMPLE);
+        let pc = repair(&p).unwrap();
+        assert_eq!(pc, 7);
+        p[pc] = p[pc].flipped();
+        assert_eq!(run(&p).unwrap(), Outcome::Terminated(8));
+    }
+
+    #[test]
+    fn trace() {
+        let mut pcs = Vec::new();
+        run_traced(&program(EXAMPLE), |pc, _, _| pcs.push(pc)).unwrap();
+        assert_eq!(pcs, vec![0, 1, 2, 6, 7, 3, 4]);
+    }
+
+    #[test]
+    fn jump_outside() {
+        assert!(run(&program("nop +0\njmp -2\n")).is_err());
+        assert_eq!(
+            run(&program("nop +0\njmp +1\n")).unwrap(),
+            Outcome::Terminated(0)
+        );
+    }
+
+    #[test]
+    fn already_terminates() {
+        assert!(repair(&program("acc +1\n")).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!("mul +1".parse::<Instruction>().is_err());
+        assert_eq!("acc 1x".parse::<Instruction>().unwrap_err().column, Some(5));
+        assert!("acc".parse::<Instruction>().is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{Error, Result};
+use std::io::Read;
+
+// Where a solution's input comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(String),
+    Inline(String),
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+impl Source {
+    // From command-line arguments, not including the program name:
+    // -e TEXT uses TEXT itself as the input,
+    // otherwise the first argument is the path to an input file,
+    // and with no arguments, input comes from standard input.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut args = args.into_iter();
+        match args.next() {
+            None => Ok(Source::Stdin),
+            Some(arg) if arg == "-e" => args
+                .next()
+                .map(Source::Inline)
+                .ok_or_else(|| Error::invalid("-e needs the input text as its argument")),
+            Some(arg) if arg == "-" => Ok(Source::Stdin),
+            Some(path) => Ok(Source::File(path)),
+        }
+    }
+
+    // For error locations.
+    pub fn name(&self) -> &str {
+        match self {
+            Source::Stdin => "<stdin>",
+            Source::File(path) => path,
+            Source::Inline(_) => "<inline>",
+        }
+    }
+
+    // Input that is gzip-compressed is decompressed, whatever its source.
+    pub fn read(&self) -> Result<String> {
+        let io_err = |e| Error::io(self.name(), e);
+        let bytes = match self {
+            Source::Stdin => {
+                let mut bytes = Vec::new();
+                std::io::stdin().read_to_end(&mut bytes).map_err(io_err)?;
+                bytes
+            }
+            Source::File(path) => std::fs::read(path).map_err(io_err)?,
+            Source::Inline(s) => return Ok(s.clone()),
+        };
+        let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut decompressed = Vec::new();
+            flate2::read::MultiGzDecoder::new(&bytes[..])
+                .read_to_end(&mut decompressed)
+                .map_err(io_err)?;
+            decompressed
+        } else {
+            bytes
+        };
+        String::from_utf8(bytes)
+            .map_err(|e| io_err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn source(args: &[&str]) -> Result<Source> {
+        Source::from_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn from_args() {
+        assert_eq!(source(&[]).unwrap(), Source::Stdin);
+        assert_eq!(source(&["-"]).unwrap(), Source::Stdin);
+        assert_eq!(
+            source(&["in.txt", "x"]).unwrap(),
+            Source::File("in.txt".to_string())
+        );
+        assert_eq!(
+            source(&["-e", "0,3,6"]).unwrap(),
+            Source::Inline("0,3,6".to_string())
+        );
+        assert!(source(&["-e"]).is_err());
+    }
+
+    #[test]
+    fn read_inline() {
+        assert_eq!(Source::Inline("0,3,6".to_string()).read().unwrap(), "0,3,6");
+    }
+
+    #[test]
+    fn read_gzip() {
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.gz", std::process::id()));
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"389125467\n").unwrap();
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let read = Source::File(path.to_string_lossy().into_owned()).read();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), "389125467\n");
+    }
+
+    #[test]
+    fn read_missing() {
+        let err = Source::File("/nonexistent/input".to_string())
+            .read()
+            .unwrap_err();
+        assert_eq!(err.path.as_deref(), Some("/nonexistent/input"));
+    }
+}
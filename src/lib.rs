@@ -1,7 +1,30 @@
+pub mod bitset;
+pub mod conway;
 pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day15;
+pub mod day17;
+pub mod day22;
+pub mod day23;
+mod error;
+pub mod graph;
+pub mod grid;
+pub mod handheld;
+pub mod input;
+pub mod opts;
+pub mod sections;
+pub mod solution;
 
-use std::env;
-use std::fs;
+pub use error::{Error, ErrorKind, OrExit, Result};
 
 #[macro_export]
 macro_rules! tests {
@@ -21,32 +44,122 @@ macro_rules! tests {
     }
 }
 
-pub fn numbers<T>(s: &str) -> Vec<T>
+// Parses a whole token, recording it in the error if it fails.
+pub fn parse<T>(s: &str) -> Result<T>
 where
     T: std::str::FromStr,
-    <T as std::str::FromStr>::Err: std::fmt::Debug,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
 {
-    s.split(|c: char| c != '-' && !c.is_digit(10))
-        .filter_map(|d| {
-            if d.is_empty() {
-                None
-            } else {
-                Some(d.parse::<T>().expect("can't parse integer"))
-            }
-        })
-        .collect()
+    s.parse::<T>().map_err(|e| Error::parse(s, e))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberMode {
+    // Only digits are part of numbers; hyphens separate like any other character.
+    Unsigned,
+    // A hyphen directly before a digit is a minus sign, so 3-5 is 3 and -5.
+    Signed,
+    // As Signed, except a hyphen directly between two digits separates a range, so 3-5 is 3 and 5.
+    SignedRanges,
+}
+
+// All the numbers in s, in the given mode.
+// Any other characters separate numbers.
+pub fn numbers_with<T>(s: &str, mode: NumberMode) -> Result<Vec<T>>
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    let bytes = s.as_bytes();
+    let digit_at = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+
+    let mut nums = Vec::new();
+    let mut start = None;
+    let mut finish = |start: &mut Option<usize>, end: usize| -> Result<()> {
+        if let Some(st) = start.take() {
+            nums.push(parse(&s[st..end]).map_err(|e| e.at_column(st + 1))?);
+        }
+        Ok(())
+    };
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if b.is_ascii_digit() {
+            start.get_or_insert(i);
+            continue;
+        }
+        finish(&mut start, i)?;
+        let sign = match mode {
+            NumberMode::Unsigned => false,
+            NumberMode::Signed => digit_at(i + 1),
+            NumberMode::SignedRanges => digit_at(i + 1) && !(i > 0 && digit_at(i - 1)),
+        };
+        if b == b'-' && sign {
+            start = Some(i);
+        }
+    }
+    finish(&mut start, s.len())?;
+
+    Ok(nums)
+}
+
+// All the numbers in s, with hyphens before digits as minus signs.
+pub fn numbers<T>(s: &str) -> Result<Vec<T>>
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    numbers_with(s, NumberMode::Signed)
 }
 
-pub fn read_input_lines<T, F>(f: F) -> Vec<T>
+// Parses each line of s, with errors located at their line.
+pub fn parse_lines<T, F>(s: &str, mut f: F) -> Result<Vec<T>>
 where
-    F: FnMut(&str) -> T,
+    F: FnMut(&str) -> Result<T>,
 {
-    read_input_file().lines().map(f).collect()
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+// Input from source, and the name to give it in errors.
+pub(crate) fn read_source(source: &input::Source) -> Result<(String, String)> {
+    let s = source.read()?;
+    Ok((source.name().to_string(), s))
 }
 
-pub fn read_input_file() -> String {
-    let filename = env::args()
-        .nth(1)
-        .unwrap_or_else(|| "/dev/stdin".to_string());
-    fs::read_to_string(filename).expect("couldn't read file")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nums(s: &str, mode: NumberMode, expected: &[i64]) {
+        assert_eq!(numbers_with::<i64>(s, mode).unwrap(), expected);
+    }
+
+    fn err_column(s: &str, mode: NumberMode, column: usize) {
+        let err = numbers_with::<u8>(s, mode).unwrap_err();
+        assert_eq!(err.column, Some(column));
+    }
+
+    tests! {
+        nums {
+            signed_simple("1,-2, 3", NumberMode::Signed, &[1, -2, 3]);
+            signed_range_is_negative("3-5", NumberMode::Signed, &[3, -5]);
+            lone_hyphen("- 1 -", NumberMode::Signed, &[1]);
+            double_hyphen("--5", NumberMode::Signed, &[-5]);
+            hyphen_after_letter("a-5", NumberMode::Signed, &[-5]);
+            unsigned_range("3-5", NumberMode::Unsigned, &[3, 5]);
+            unsigned_ignores_sign("x=-3", NumberMode::Unsigned, &[3]);
+            ranges("1-3 a: abcde", NumberMode::SignedRanges, &[1, 3]);
+            negative_ranges("-3--5", NumberMode::SignedRanges, &[-3, -5]);
+            ranges_keep_signs("x=-3..5, y=-1", NumberMode::SignedRanges, &[-3, 5, -1]);
+            empty("", NumberMode::Signed, &[]);
+            no_numbers("abc-", NumberMode::SignedRanges, &[]);
+            trailing("ab12", NumberMode::Unsigned, &[12]);
+        }
+        err_column {
+            overflow("1 2 300", NumberMode::Unsigned, 5);
+            negative_into_unsigned("1,-2", NumberMode::Signed, 3);
+        }
+    }
 }
@@ -0,0 +1,320 @@
+// Command-line options shared by all binaries, plus any day-specific parameters.
+//
+// Every binary accepts -h/--help, -v/--verbose, --part 1|2, --time, --json,
+// and its input as described in the README (a file path, -e TEXT, or standard input).
+
+use crate::input::Source;
+use crate::{Error, Result};
+use std::collections::HashMap;
+
+// A day-specific option.
+pub struct Param {
+    pub short: Option<char>,
+    pub long: &'static str,
+    // What to call the value in the usage text, or None for an option that takes no value.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+pub struct Opts {
+    pub verbose: bool,
+    // Only run this part, if given.
+    pub part: Option<u8>,
+    pub time: bool,
+    // Print answers and timings as JSON instead.
+    pub json: bool,
+    // Arguments that aren't options, in order, with -e TEXT kept together.
+    pub args: Vec<String>,
+    values: HashMap<&'static str, String>,
+    flags: Vec<&'static str>,
+}
+
+const COMMON: &[Param] = &[
+    Param {
+        short: Some('h'),
+        long: "help",
+        value: None,
+        help: "show this help",
+    },
+    Param {
+        short: Some('v'),
+        long: "verbose",
+        value: None,
+        help: "print extra information",
+    },
+    Param {
+        short: None,
+        long: "part",
+        value: Some("1|2"),
+        help: "only run the given part",
+    },
+    Param {
+        short: None,
+        long: "time",
+        value: None,
+        help: "print how long each part takes, on standard error",
+    },
+    Param {
+        short: None,
+        long: "json",
+        value: None,
+        help: "print answers and timings as one line of JSON",
+    },
+];
+
+impl Opts {
+    pub fn wants_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    pub fn source(&self) -> Result<Source> {
+        Source::from_args(self.args.iter().cloned())
+    }
+
+    pub fn flag(&self, long: &str) -> bool {
+        self.flags.contains(&long)
+    }
+
+    pub fn get<T>(&self, long: &str) -> Result<Option<T>>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.values
+            .get(long)
+            .map(|v| crate::parse(v).map_err(|e| Error::invalid(format!("--{}: {}", long, e))))
+            .transpose()
+    }
+
+    pub fn get_or<T>(&self, long: &str, default: T) -> Result<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        Ok(self.get(long)?.unwrap_or(default))
+    }
+}
+
+fn find<'a>(params: &'a [Param], arg: &str) -> Option<(&'a Param, Option<String>)> {
+    let all = COMMON.iter().chain(params);
+    if let Some(long) = arg.strip_prefix("--") {
+        let (name, value) = match long.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (long, None),
+        };
+        return all.into_iter().find(|p| p.long == name).map(|p| (p, value));
+    }
+    let mut chars = arg.strip_prefix('-')?.chars();
+    let short = chars.next()?;
+    let rest: String = chars.collect();
+    let param = all.into_iter().find(|p| p.short == Some(short))?;
+    // Values may be attached, as in -d5.
+    match (param.value, rest.is_empty()) {
+        (_, true) => Some((param, None)),
+        (Some(_), false) => Some((param, Some(rest))),
+        (None, false) => None,
+    }
+}
+
+// Parses arguments, not including the program name.
+// Returns None if help was requested.
+pub fn parse<I>(params: &[Param], args: I) -> Result<Option<Opts>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut opts = Opts {
+        verbose: false,
+        part: None,
+        time: false,
+        json: false,
+        args: Vec::new(),
+        values: HashMap::new(),
+        flags: Vec::new(),
+    };
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "-e" {
+            opts.args.push(arg);
+            opts.args.extend(args.next());
+            continue;
+        }
+        if arg == "-" || !arg.starts_with('-') {
+            opts.args.push(arg);
+            continue;
+        }
+
+        let (param, value) =
+            find(params, &arg).ok_or_else(|| Error::invalid(format!("unknown option {}", arg)))?;
+        if param.value.is_none() {
+            if value.is_some() {
+                return Err(Error::invalid(format!("--{} takes no value", param.long)));
+            }
+            match param.long {
+                "help" => return Ok(None),
+                "verbose" => opts.verbose = true,
+                "time" => opts.time = true,
+                "json" => opts.json = true,
+                long => opts.flags.push(long),
+            }
+            continue;
+        }
+
+        let value = value
+            .or_else(|| args.next())
+            .ok_or_else(|| Error::invalid(format!("--{} needs a value", param.long)))?;
+        if param.long == "part" {
+            opts.part = match value.as_str() {
+                "1" => Some(1),
+                "2" => Some(2),
+                _ => {
+                    return Err(Error::invalid(format!(
+                        "--part must be 1 or 2, not {}",
+                        value
+                    )))
+                }
+            };
+        } else {
+            opts.values.insert(param.long, value);
+        }
+    }
+
+    Ok(Some(opts))
+}
+
+pub fn usage(program: &str, params: &[Param]) -> String {
+    let mut s = format!(
+        "usage: {} [options] [input file | -e input text]\n\noptions:\n",
+        program
+    );
+    for param in COMMON.iter().chain(params) {
+        let short = param
+            .short
+            .map_or("    ".to_string(), |c| format!("-{}, ", c));
+        let long = match param.value {
+            Some(value) => format!("--{} {}", param.long, value),
+            None => format!("--{}", param.long),
+        };
+        s.push_str(&format!("  {}{:<16} {}\n", short, long, param.help));
+    }
+    s
+}
+
+// Parses this process's arguments.
+// Prints usage and exits on --help (successfully) or on a bad option (unsuccessfully).
+pub fn from_env(params: &[Param]) -> Opts {
+    let mut args = std::env::args();
+    let program = args.next().unwrap_or_default();
+    let program = std::path::Path::new(&program)
+        .file_name()
+        .map_or(program.clone(), |f| f.to_string_lossy().into_owned());
+
+    match parse(params, args) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            print!("{}", usage(&program, params));
+            std::process::exit(0)
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            eprint!("{}", usage(&program, params));
+            std::process::exit(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param {
+            short: Some('d'),
+            long: "dim",
+            value: Some("N"),
+            help: "dimensions",
+        },
+        Param {
+            short: Some('p'),
+            long: "periodic",
+            value: None,
+            help: "run until periodic",
+        },
+    ];
+
+    fn parse_ok(args: &[&str]) -> Opts {
+        parse(PARAMS, args.iter().map(|s| s.to_string()))
+            .unwrap()
+            .expect("wanted opts, not help")
+    }
+
+    fn parse_err(args: &[&str]) -> Error {
+        parse(PARAMS, args.iter().map(|s| s.to_string()))
+            .err()
+            .expect("wanted an error")
+    }
+
+    #[test]
+    fn common() {
+        let opts = parse_ok(&["-v", "--part", "2", "--time", "in.txt"]);
+        assert!(opts.verbose);
+        assert!(opts.time);
+        assert!(!opts.wants_part(1));
+        assert!(opts.wants_part(2));
+        assert_eq!(opts.source().unwrap(), Source::File("in.txt".to_string()));
+    }
+
+    #[test]
+    fn defaults() {
+        let opts = parse_ok(&[]);
+        assert!(!opts.verbose && !opts.time && !opts.flag("periodic"));
+        assert!(opts.wants_part(1) && opts.wants_part(2));
+        assert_eq!(opts.source().unwrap(), Source::Stdin);
+        assert_eq!(opts.get_or("dim", 3_u16).unwrap(), 3);
+    }
+
+    #[test]
+    fn values() {
+        for args in [&["-d5"][..], &["-d", "5"], &["--dim", "5"], &["--dim=5"]] {
+            assert_eq!(parse_ok(args).get::<u16>("dim").unwrap(), Some(5));
+        }
+        assert!(parse_ok(&["-d", "x"]).get::<u16>("dim").is_err());
+    }
+
+    #[test]
+    fn flags() {
+        assert!(parse_ok(&["-p"]).flag("periodic"));
+        assert!(parse_ok(&["--periodic"]).flag("periodic"));
+    }
+
+    #[test]
+    fn inline_input() {
+        let opts = parse_ok(&["-e", "-3", "-v"]);
+        assert_eq!(opts.source().unwrap(), Source::Inline("-3".to_string()));
+        assert!(opts.verbose);
+    }
+
+    #[test]
+    fn help() {
+        assert!(parse(PARAMS, ["--help".to_string()]).unwrap().is_none());
+        assert!(parse(PARAMS, ["-h".to_string()]).unwrap().is_none());
+    }
+
+    #[test]
+    fn errors() {
+        parse_err(&["--nope"]);
+        parse_err(&["-x"]);
+        parse_err(&["--part", "3"]);
+        parse_err(&["--dim"]);
+        parse_err(&["-vx"]);
+        parse_err(&["--time=1"]);
+    }
+
+    #[test]
+    fn usage_lists_params() {
+        let u = usage("prog", PARAMS);
+        assert!(u.starts_with("usage: prog "));
+        assert!(u.contains("  -d, --dim N "));
+        assert!(u.contains("      --part 1|2 "));
+    }
+}
@@ -0,0 +1,135 @@
+// Input split into blank-line-separated paragraphs,
+// optionally each starting with a "Header:" line,
+// keeping line numbers so that errors can say where they happened.
+
+use crate::{Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    // Counting from 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paragraph<'a> {
+    pub lines: Vec<Line<'a>>,
+}
+
+impl<'a> Paragraph<'a> {
+    pub fn first_line(&self) -> usize {
+        self.lines.first().map_or(0, |line| line.number)
+    }
+
+    pub fn texts(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.lines.iter().map(|line| line.text)
+    }
+
+    // Parses each line, with errors located at their line.
+    pub fn parse_lines<T, F>(&self, mut f: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&str) -> Result<T>,
+    {
+        self.lines
+            .iter()
+            .map(|line| f(line.text).map_err(|e| e.at_line(line.number)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    // Without the trailing colon.
+    pub header: &'a str,
+    pub header_line: usize,
+    // May be empty, if the header was the only line.
+    pub body: Paragraph<'a>,
+}
+
+// Lines containing only whitespace separate paragraphs.
+// Runs of several such lines do not make empty paragraphs.
+pub fn paragraphs(s: &str) -> Vec<Paragraph<'_>> {
+    let mut paragraphs = Vec::new();
+    let mut lines = Vec::new();
+    for (i, text) in s.lines().enumerate() {
+        if text.trim().is_empty() {
+            if !lines.is_empty() {
+                paragraphs.push(Paragraph {
+                    lines: std::mem::take(&mut lines),
+                });
+            }
+        } else {
+            lines.push(Line {
+                number: i + 1,
+                text,
+            });
+        }
+    }
+    if !lines.is_empty() {
+        paragraphs.push(Paragraph { lines });
+    }
+    paragraphs
+}
+
+// Paragraphs that each start with a line ending in a colon, such as "Player 1:".
+pub fn sections(s: &str) -> Result<Vec<Section<'_>>> {
+    paragraphs(s)
+        .into_iter()
+        .map(|mut paragraph| {
+            let first = paragraph.lines.remove(0);
+            match first.text.trim_end().strip_suffix(':') {
+                Some(header) => Ok(Section {
+                    header,
+                    header_line: first.number,
+                    body: paragraph,
+                }),
+                None => {
+                    Err(Error::parse(first.text, "expected a header ending in :")
+                        .at_line(first.number))
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_paragraphs() {
+        let ps = paragraphs("a\nb\n\n\n c\n  \nd\n");
+        let texts: Vec<Vec<_>> = ps.iter().map(|p| p.texts().collect()).collect();
+        assert_eq!(texts, vec![vec!["a", "b"], vec![" c"], vec!["d"]]);
+        let firsts: Vec<_> = ps.iter().map(Paragraph::first_line).collect();
+        assert_eq!(firsts, vec![1, 5, 7]);
+    }
+
+    #[test]
+    fn no_paragraphs() {
+        assert!(paragraphs("").is_empty());
+        assert!(paragraphs("\n\n").is_empty());
+    }
+
+    #[test]
+    fn headers() {
+        let ss = sections("Player 1:\n9\n2\n\nPlayer 2:\n5\n").unwrap();
+        assert_eq!(ss.len(), 2);
+        assert_eq!(ss[1].header, "Player 2");
+        assert_eq!(ss[1].header_line, 5);
+        assert_eq!(ss[1].body.texts().collect::<Vec<_>>(), vec!["5"]);
+    }
+
+    #[test]
+    fn missing_header() {
+        let err = sections("Player 1:\n9\n\n5\n").unwrap_err();
+        assert_eq!(err.line, Some(4));
+    }
+
+    #[test]
+    fn parse_error_line() {
+        let ss = sections("Player 1:\n9\nx\n").unwrap();
+        let err = ss[0].body.parse_lines(crate::parse::<u8>).unwrap_err();
+        assert_eq!(err.line, Some(3));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::opts::Opts;
+use crate::{OrExit, Result};
+use std::time::{Duration, Instant};
+
+pub trait Solution {
+    const DAY: u8;
+    const NAME: &'static str;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<String>;
+    fn part2(input: &Self::Input) -> Result<String>;
+}
+
+pub struct Part {
+    pub part: u8,
+    pub answer: String,
+    pub time: Duration,
+}
+
+pub struct Report {
+    pub day: u8,
+    pub name: &'static str,
+    pub parse_time: Duration,
+    pub parts: Vec<Part>,
+}
+
+impl Report {
+    // One line, with times in nanoseconds.
+    pub fn to_json(&self) -> String {
+        let parts: Vec<_> = self
+            .parts
+            .iter()
+            .map(|p| {
+                format!(
+                    "{{\"part\":{},\"answer\":{},\"ns\":{}}}",
+                    p.part,
+                    json_string(&p.answer),
+                    p.time.as_nanos()
+                )
+            })
+            .collect();
+        format!(
+            "{{\"day\":{},\"name\":{},\"parse_ns\":{},\"parts\":[{}]}}",
+            self.day,
+            json_string(self.name),
+            self.parse_time.as_nanos(),
+            parts.join(",")
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub fn format_ms(d: Duration) -> String {
+    format!("{:.3} ms", d.as_secs_f64() * 1000.0)
+}
+
+type PartFn<I> = fn(&I) -> Result<String>;
+
+pub fn run<S: Solution>(input: &str) -> Result<Report> {
+    let t = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = t.elapsed();
+    let parts: [PartFn<S::Input>; 2] = [S::part1, S::part2];
+    let parts = (1..)
+        .zip(parts)
+        .map(|(part, f)| {
+            let t = Instant::now();
+            let answer = f(&input)?;
+            Ok(Part {
+                part,
+                answer,
+                time: t.elapsed(),
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(Report {
+        day: S::DAY,
+        name: S::NAME,
+        parse_time,
+        parts,
+    })
+}
+
+// Times and reports parsing and each part for a binary,
+// printing answers as they come, or everything as JSON at the end with --json.
+pub struct Timings<'a> {
+    opts: &'a Opts,
+    report: Report,
+}
+
+impl<'a> Timings<'a> {
+    pub fn new(opts: &'a Opts, day: u8, name: &'static str) -> Self {
+        Self {
+            opts,
+            report: Report {
+                day,
+                name,
+                parse_time: Duration::ZERO,
+                parts: Vec::new(),
+            },
+        }
+    }
+
+    pub fn parse<T, F: FnOnce() -> T>(&mut self, f: F) -> T {
+        let t = Instant::now();
+        let parsed = f();
+        self.report.parse_time = t.elapsed();
+        if self.opts.time && !self.opts.json {
+            eprintln!("parse: {}", format_ms(self.report.parse_time));
+        }
+        parsed
+    }
+
+    // Runs the part, if it was asked for.
+    pub fn part<F: FnOnce() -> Result<String>>(&mut self, part: u8, f: F) {
+        if !self.opts.wants_part(part) {
+            return;
+        }
+        let t = Instant::now();
+        let answer = f().or_exit();
+        let time = t.elapsed();
+        if !self.opts.json {
+            println!("{}", answer);
+            if self.opts.time {
+                eprintln!("part {}: {}", part, format_ms(time));
+            }
+        }
+        self.report.parts.push(Part { part, answer, time });
+    }
+
+    pub fn finish(self) {
+        if self.opts.json {
+            println!("{}", self.report.to_json());
+        }
+    }
+}
+
+// A registered solution, with its input type erased so that all days fit in one list.
+pub struct Entry {
+    pub day: u8,
+    pub name: &'static str,
+    pub run: fn(&str) -> Result<Report>,
+}
+
+const fn entry<S: Solution>() -> Entry {
+    Entry {
+        day: S::DAY,
+        name: S::NAME,
+        run: run::<S>,
+    }
+}
+
+pub const SOLUTIONS: &[Entry] = &[
+    entry::<crate::day01::Day01>(),
+    entry::<crate::day02::Day02>(),
+    entry::<crate::day03::Day03>(),
+    entry::<crate::day04::Day04>(),
+    entry::<crate::day05::Day05>(),
+    entry::<crate::day06::Day06>(),
+    entry::<crate::day07::Day07>(),
+    entry::<crate::day08::Day08>(),
+    entry::<crate::day09::Day09>(),
+    entry::<crate::day10::Day10>(),
+    entry::<crate::day11::Day11>(),
+    entry::<crate::day15::Day15>(),
+    entry::<crate::day17::Day17>(),
+    entry::<crate::day22::Day22>(),
+    entry::<crate::day23::Day23>(),
+];
+
+pub fn find(day: u8) -> Option<&'static Entry> {
+    SOLUTIONS.iter().find(|entry| entry.day == day)
+}
+
+// For binaries that do nothing beyond printing each part's answer.
+pub fn main<S: Solution>() {
+    main_with::<S>(&crate::opts::from_env(&[]));
+}
+
+pub fn main_with<S: Solution>(opts: &Opts) {
+    let (filename, input) = opts
+        .source()
+        .and_then(|source| crate::read_source(&source))
+        .or_exit();
+    let mut timings = Timings::new(opts, S::DAY, S::NAME);
+    let input = timings
+        .parse(|| S::parse(&input))
+        .map_err(|e| e.in_file(&filename))
+        .or_exit();
+    timings.part(1, || S::part1(&input));
+    timings.part(2, || S::part2(&input));
+    timings.finish();
+}
+
+#[cfg(test)]
+pub(crate) fn check_part1<S: Solution>(input: &str, expected: &str) {
+    let input = S::parse(input).expect("couldn't parse input");
+    assert_eq!(S::part1(&input).expect("part 1 failed"), expected);
+}
+
+#[cfg(test)]
+pub(crate) fn check_part2<S: Solution>(input: &str, expected: &str) {
+    let input = S::parse(input).expect("couldn't parse input");
+    assert_eq!(S::part2(&input).expect("part 2 failed"), expected);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_json() {
+        let report = Report {
+            day: 4,
+            name: "Passport \"Processing\"",
+            parse_time: Duration::from_nanos(1500),
+            parts: vec![
+                Part {
+                    part: 1,
+                    answer: "2".to_string(),
+                    time: Duration::from_nanos(30),
+                },
+                Part {
+                    part: 2,
+                    answer: "a\\b\nc\u{1}".to_string(),
+                    time: Duration::from_micros(2),
+                },
+            ],
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":4,"name":"Passport \"Processing\"","parse_ns":1500,"parts":[{"part":1,"answer":"2","ns":30},{"part":2,"answer":"a\\b\nc\u0001","ns":2000}]}"#
+        );
+    }
+}
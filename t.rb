@@ -1 +0,0 @@
-test.rb
\ No newline at end of file
@@ -0,0 +1,63 @@
+// Runs every registered solution on each <day>.in in the cases directory,
+// comparing its answers against the matching <day>.out (one answer per line).
+// The directory is AOC_CASES_DIR if set, otherwise the bundled examples in cases/.
+
+use adventofcode::solution::find;
+use std::path::{Path, PathBuf};
+
+fn cases_dir() -> PathBuf {
+    std::env::var_os("AOC_CASES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("cases"))
+}
+
+// The day is the leading digits of the file name, so 17.in and 17p.in are both day 17.
+fn day_of(path: &Path) -> Option<u8> {
+    let stem = path.file_stem()?.to_str()?;
+    let digits: String = stem.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+#[test]
+fn expected_answers() {
+    let dir = cases_dir();
+    let mut inputs: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("couldn't read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("couldn't read directory entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "in"))
+        .collect();
+    inputs.sort();
+
+    let mut failures = Vec::new();
+    let mut checked = 0;
+
+    for input in inputs {
+        let Some(entry) = day_of(&input).and_then(find) else {
+            continue;
+        };
+        let Ok(expected) = std::fs::read_to_string(input.with_extension("out")) else {
+            continue;
+        };
+        let expected: Vec<_> = expected.lines().collect();
+
+        let s = std::fs::read_to_string(&input).expect("couldn't read file");
+        match (entry.run)(&s) {
+            Ok(report) => {
+                let answers: Vec<_> = report.parts.iter().map(|p| p.answer.as_str()).collect();
+                if answers != expected {
+                    failures.push(format!(
+                        "{}: expected {:?}, got {:?}",
+                        input.display(),
+                        expected,
+                        answers
+                    ));
+                }
+            }
+            Err(e) => failures.push(format!("{}: {}", input.display(), e)),
+        }
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no cases with answers in {}", dir.display());
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}